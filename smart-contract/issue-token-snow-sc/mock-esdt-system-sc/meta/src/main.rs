@@ -0,0 +1,3 @@
+fn main() {
+    multiversx_sc_meta_lib::cli_main::<mock_esdt_system_sc::AbiProvider>();
+}
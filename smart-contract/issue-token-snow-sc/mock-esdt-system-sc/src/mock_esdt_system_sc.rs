@@ -0,0 +1,127 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+
+/// Suffix of the identifiers returned for issued collections
+const COLLECTION_RANDOM_SUFFIX: &[u8] = b"-123456";
+
+/// Minimal ESDT system smart contract used by the Go VM scenarios, deployed at the system address.
+/// Accepts or rejects every token management and issue request depending on an owner-set flag,
+/// and records the name of each accepted request so scenarios can check what was called.
+/// Collection issuances return the ticker followed by a fixed random suffix as identifier.
+/// The Rust VM simulates the system smart contract itself and never reaches this mock.
+#[multiversx_sc::contract]
+pub trait MockEsdtSystemSc {
+    #[init]
+    fn init(&self) {}
+
+    /// Storage to hold whether every request is rejected
+    #[view(isRejecting)]
+    #[storage_mapper("rejecting")]
+    fn rejecting(&self) -> SingleValueMapper<bool>;
+
+    /// Storage to hold the names of the accepted requests, in call order
+    #[view(getCalls)]
+    #[storage_mapper("calls")]
+    fn calls(&self) -> VecMapper<ManagedBuffer>;
+
+    /// Endpoint to accept or reject the following requests
+    #[endpoint(setRejecting)]
+    fn set_rejecting(&self, rejecting: bool) {
+        self.rejecting().set(rejecting);
+    }
+
+    #[payable("EGLD")]
+    #[endpoint(issue)]
+    fn issue(&self, _args: MultiValueEncoded<ManagedBuffer>) {
+        self.record_call("issue");
+    }
+
+    #[payable("EGLD")]
+    #[endpoint(issueNonFungible)]
+    fn issue_non_fungible(
+        &self,
+        _name: ManagedBuffer,
+        ticker: ManagedBuffer,
+        _args: MultiValueEncoded<ManagedBuffer>,
+    ) -> TokenIdentifier {
+        self.record_call("issueNonFungible");
+        self.collection_identifier(ticker)
+    }
+
+    #[payable("EGLD")]
+    #[endpoint(issueSemiFungible)]
+    fn issue_semi_fungible(
+        &self,
+        _name: ManagedBuffer,
+        ticker: ManagedBuffer,
+        _args: MultiValueEncoded<ManagedBuffer>,
+    ) -> TokenIdentifier {
+        self.record_call("issueSemiFungible");
+        self.collection_identifier(ticker)
+    }
+
+    #[payable("EGLD")]
+    #[endpoint(registerMetaESDT)]
+    fn register_meta_esdt(
+        &self,
+        _name: ManagedBuffer,
+        ticker: ManagedBuffer,
+        _args: MultiValueEncoded<ManagedBuffer>,
+    ) -> TokenIdentifier {
+        self.record_call("registerMetaESDT");
+        self.collection_identifier(ticker)
+    }
+
+    #[endpoint(setSpecialRole)]
+    fn set_special_role(&self, _args: MultiValueEncoded<ManagedBuffer>) {
+        self.record_call("setSpecialRole");
+    }
+
+    #[endpoint(unSetSpecialRole)]
+    fn unset_special_role(&self, _args: MultiValueEncoded<ManagedBuffer>) {
+        self.record_call("unSetSpecialRole");
+    }
+
+    #[endpoint(freeze)]
+    fn freeze(&self, _args: MultiValueEncoded<ManagedBuffer>) {
+        self.record_call("freeze");
+    }
+
+    #[endpoint(unFreeze)]
+    fn unfreeze(&self, _args: MultiValueEncoded<ManagedBuffer>) {
+        self.record_call("unFreeze");
+    }
+
+    #[endpoint(wipe)]
+    fn wipe(&self, _args: MultiValueEncoded<ManagedBuffer>) {
+        self.record_call("wipe");
+    }
+
+    #[endpoint(pause)]
+    fn pause(&self, _args: MultiValueEncoded<ManagedBuffer>) {
+        self.record_call("pause");
+    }
+
+    #[endpoint(unPause)]
+    fn unpause(&self, _args: MultiValueEncoded<ManagedBuffer>) {
+        self.record_call("unPause");
+    }
+
+    /// Builds the identifier returned for an issued collection
+    fn collection_identifier(&self, ticker: ManagedBuffer) -> TokenIdentifier {
+        let mut identifier = ticker;
+        identifier.append_bytes(COLLECTION_RANDOM_SUFFIX);
+        TokenIdentifier::from(identifier)
+    }
+
+    /// Fails when requests are rejected, otherwise records the request name
+    fn record_call(&self, name: &str) {
+        require!(!self.rejecting().get(), "Request rejected");
+
+        self.calls().push(&ManagedBuffer::from(name));
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
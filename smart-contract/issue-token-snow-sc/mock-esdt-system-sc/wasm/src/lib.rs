@@ -0,0 +1,40 @@
+// Code generated by the multiversx-sc build system. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+// Init:                                 1
+// Upgrade:                              1
+// Endpoints:                            14
+// Async Callback (empty):               1
+// Total number of exported functions:   17
+
+#![no_std]
+
+multiversx_sc_wasm_adapter::allocator!();
+multiversx_sc_wasm_adapter::panic_handler!();
+
+multiversx_sc_wasm_adapter::endpoints! {
+    mock_esdt_system_sc
+    (
+        init => init
+        upgrade => upgrade
+        isRejecting => rejecting
+        getCalls => calls
+        setRejecting => set_rejecting
+        issue => issue
+        issueNonFungible => issue_non_fungible
+        issueSemiFungible => issue_semi_fungible
+        registerMetaESDT => register_meta_esdt
+        setSpecialRole => set_special_role
+        unSetSpecialRole => unset_special_role
+        freeze => freeze
+        unFreeze => unfreeze
+        wipe => wipe
+        pause => pause
+        unPause => unpause
+    )
+}
+
+multiversx_sc_wasm_adapter::async_callback_empty! {}
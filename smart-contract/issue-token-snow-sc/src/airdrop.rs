@@ -0,0 +1,245 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+use crate::role_management;
+
+/// Number of claim flags stored in one bitmap word
+const BITMAP_WORD_BITS: u64 = 64;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct AirdropDistribution<M: ManagedTypeApi> {
+    pub distribution_id: u64,
+    pub creator: ManagedAddress<M>,
+    pub merkle_root: ManagedByteArray<M, 32>,
+    pub total_amount: BigUint<M>,
+    pub claimed_amount: BigUint<M>,
+    pub expiry_timestamp: u64,
+}
+
+/// Module distributing tokens to many recipients through Merkle-proof claims.
+#[multiversx_sc::module]
+pub trait AirdropModule: role_management::RoleManagementModule {
+    /// Storage to hold the active airdrop distribution of each token
+    #[view(get_airdrop)]
+    #[storage_mapper("airdrop_distribution")]
+    fn airdrop_distribution(
+        &self,
+        token_identifier: &TokenIdentifier,
+    ) -> SingleValueMapper<AirdropDistribution<Self::Api>>;
+
+    /// Storage to hold the claim flags of each distribution, 64 leaves per word
+    #[storage_mapper("airdrop_claimed_bitmap")]
+    fn airdrop_claimed_bitmap(&self, distribution_id: u64, word_index: u64) -> SingleValueMapper<u64>;
+
+    /// Storage to hold the id of the last created distribution
+    #[storage_mapper("last_airdrop_id")]
+    fn last_airdrop_id(&self) -> SingleValueMapper<u64>;
+
+    /// Endpoint to fund an airdrop distribution for the paid token
+    #[payable("*")]
+    #[endpoint(create_airdrop)]
+    fn create_airdrop(&self, merkle_root: ManagedByteArray<Self::Api, 32>, expiry_timestamp: u64) {
+        let caller = self.blockchain().get_caller();
+        let (token_identifier, amount) = self.call_value().single_fungible_esdt();
+
+        self.require_token_issuer(&token_identifier);
+        require!(amount > 0, "Airdrop amount must be greater than zero");
+        require!(
+            self.airdrop_distribution(&token_identifier).is_empty(),
+            "An airdrop is already active for this token"
+        );
+        require!(
+            expiry_timestamp > self.blockchain().get_block_timestamp(),
+            "Airdrop expiry must be in the future"
+        );
+
+        let distribution_id = self.last_airdrop_id().get() + 1;
+        self.last_airdrop_id().set(distribution_id);
+
+        self.airdrop_distribution(&token_identifier).set(AirdropDistribution {
+            distribution_id,
+            creator: caller,
+            merkle_root: merkle_root.clone(),
+            total_amount: amount.clone(),
+            claimed_amount: BigUint::zero(),
+            expiry_timestamp,
+        });
+
+        self.airdrop_created_event(token_identifier, merkle_root, expiry_timestamp, amount);
+    }
+
+    /// Endpoint to claim an airdrop allocation with a Merkle proof.
+    /// Besides the token, amount and proof, the caller passes the `index` of their leaf, which
+    /// is hashed into the leaf and locates their flag in the claimed bitmap.
+    #[endpoint(claim_airdrop)]
+    fn claim_airdrop(
+        &self,
+        token_identifier: TokenIdentifier,
+        index: u64,
+        amount: BigUint,
+        proof: MultiValueEncoded<ManagedByteArray<Self::Api, 32>>,
+    ) {
+        let caller = self.blockchain().get_caller();
+
+        require!(
+            !self.airdrop_distribution(&token_identifier).is_empty(),
+            "No airdrop is active for this token"
+        );
+        let mut distribution = self.airdrop_distribution(&token_identifier).get();
+
+        require!(
+            self.blockchain().get_block_timestamp() < distribution.expiry_timestamp,
+            "Airdrop has expired"
+        );
+        require!(
+            !self.is_claimed(distribution.distribution_id, index),
+            "Airdrop already claimed"
+        );
+
+        let leaf = self.compute_airdrop_leaf(index, &caller, &amount);
+        require!(
+            self.verify_merkle_proof(leaf, proof, &distribution.merkle_root),
+            "Invalid Merkle proof"
+        );
+
+        distribution.claimed_amount += &amount;
+        require!(
+            distribution.claimed_amount <= distribution.total_amount,
+            "Airdrop funds exhausted"
+        );
+
+        self.set_claimed(distribution.distribution_id, index);
+        self.airdrop_distribution(&token_identifier).set(distribution);
+
+        self.send().direct_esdt(&caller, &token_identifier, 0, &amount);
+
+        self.airdrop_claimed_event(token_identifier, caller, index, amount);
+    }
+
+    /// Endpoint for the owner or the distribution creator to return the unclaimed tokens to the
+    /// creator after expiry
+    #[endpoint(reclaim_airdrop)]
+    fn reclaim_airdrop(&self, token_identifier: TokenIdentifier) {
+        let caller = self.blockchain().get_caller();
+
+        require!(
+            !self.airdrop_distribution(&token_identifier).is_empty(),
+            "No airdrop is active for this token"
+        );
+        let distribution = self.airdrop_distribution(&token_identifier).get();
+
+        require!(
+            caller == distribution.creator || caller == self.blockchain().get_owner_address(),
+            "Only the owner or the airdrop creator can reclaim the tokens"
+        );
+        require!(
+            self.blockchain().get_block_timestamp() >= distribution.expiry_timestamp,
+            "Airdrop has not expired yet"
+        );
+
+        let unclaimed = &distribution.total_amount - &distribution.claimed_amount;
+        self.airdrop_distribution(&token_identifier).clear();
+
+        if unclaimed > 0 {
+            self.send()
+                .direct_esdt(&distribution.creator, &token_identifier, 0, &unclaimed);
+        }
+
+        self.airdrop_reclaimed_event(token_identifier, distribution.creator, unclaimed);
+    }
+
+    /// View endpoint to check whether a leaf of the active distribution of a token was claimed
+    #[view(is_airdrop_claimed)]
+    fn is_airdrop_claimed(&self, token_identifier: TokenIdentifier, index: u64) -> bool {
+        if self.airdrop_distribution(&token_identifier).is_empty() {
+            return false;
+        }
+
+        let distribution = self.airdrop_distribution(&token_identifier).get();
+        self.is_claimed(distribution.distribution_id, index)
+    }
+
+    /// Computes the leaf of an allocation as keccak256(keccak256(index | address | amount)).
+    /// Hashing twice keeps leaves distinct from the 64-byte inner nodes of the tree.
+    fn compute_airdrop_leaf(
+        &self,
+        index: u64,
+        address: &ManagedAddress,
+        amount: &BigUint,
+    ) -> ManagedByteArray<Self::Api, 32> {
+        let mut leaf_data = ManagedBuffer::new();
+        leaf_data.append_bytes(&index.to_be_bytes());
+        leaf_data.append(address.as_managed_buffer());
+        leaf_data.append(&amount.to_bytes_be_buffer());
+
+        let inner_hash = self.crypto().keccak256(&leaf_data);
+        self.crypto().keccak256(inner_hash.as_managed_buffer())
+    }
+
+    /// Verifies a Merkle proof built with sorted pairs: each node is keccak256(min(a, b) | max(a, b)).
+    fn verify_merkle_proof(
+        &self,
+        leaf: ManagedByteArray<Self::Api, 32>,
+        proof: MultiValueEncoded<ManagedByteArray<Self::Api, 32>>,
+        merkle_root: &ManagedByteArray<Self::Api, 32>,
+    ) -> bool {
+        let mut computed_hash = leaf;
+
+        for proof_element in proof.into_iter() {
+            let mut pair = ManagedBuffer::new();
+            if computed_hash.to_byte_array() <= proof_element.to_byte_array() {
+                pair.append(computed_hash.as_managed_buffer());
+                pair.append(proof_element.as_managed_buffer());
+            } else {
+                pair.append(proof_element.as_managed_buffer());
+                pair.append(computed_hash.as_managed_buffer());
+            }
+
+            computed_hash = self.crypto().keccak256(&pair);
+        }
+
+        &computed_hash == merkle_root
+    }
+
+    fn is_claimed(&self, distribution_id: u64, index: u64) -> bool {
+        let word = self
+            .airdrop_claimed_bitmap(distribution_id, index / BITMAP_WORD_BITS)
+            .get();
+        word & (1u64 << (index % BITMAP_WORD_BITS)) != 0
+    }
+
+    fn set_claimed(&self, distribution_id: u64, index: u64) {
+        self.airdrop_claimed_bitmap(distribution_id, index / BITMAP_WORD_BITS)
+            .update(|word| *word |= 1u64 << (index % BITMAP_WORD_BITS));
+    }
+
+    /// Event emitted when an airdrop distribution is created.
+    #[event("airdrop_created")]
+    fn airdrop_created_event(
+        &self,
+        #[indexed] token_identifier: TokenIdentifier,
+        #[indexed] merkle_root: ManagedByteArray<Self::Api, 32>,
+        #[indexed] expiry_timestamp: u64,
+        total_amount: BigUint,
+    );
+
+    /// Event emitted when an airdrop allocation is claimed.
+    #[event("airdrop_claimed")]
+    fn airdrop_claimed_event(
+        &self,
+        #[indexed] token_identifier: TokenIdentifier,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] index: u64,
+        amount: BigUint,
+    );
+
+    /// Event emitted when the unclaimed tokens of an expired airdrop are reclaimed.
+    #[event("airdrop_reclaimed")]
+    fn airdrop_reclaimed_event(
+        &self,
+        #[indexed] token_identifier: TokenIdentifier,
+        #[indexed] creator: ManagedAddress,
+        amount: BigUint,
+    );
+}
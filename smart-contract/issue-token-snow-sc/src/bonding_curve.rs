@@ -0,0 +1,409 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+/// Denominator of the exponential growth rate, expressed in basis points
+const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Maximum growth per price step accepted for exponential curves (100%)
+const MAX_GROWTH_BPS: u64 = 10_000;
+
+/// Maximum number of price steps of an exponential curve, keeps the price computation bounded
+const MAX_EXPONENTIAL_STEPS: u64 = 1_000;
+
+/// Maximum number of price steps a single trade can walk through
+const MAX_STEPS_PER_TRADE: u32 = 100;
+
+/// How the price evolves from one step of the curve to the next
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq)]
+pub enum CurveKind {
+    /// The price grows by a fixed EGLD amount per step
+    Linear,
+    /// The price grows by a fixed percentage (in basis points) per step
+    Exponential,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct TokenSale<M: ManagedTypeApi> {
+    pub issuer: ManagedAddress<M>,
+    pub kind: CurveKind,
+    pub step_size: BigUint<M>,
+    pub initial_price: BigUint<M>,
+    pub price_factor: BigUint<M>,
+    pub listed_amount: BigUint<M>,
+    pub sold_amount: BigUint<M>,
+    pub reserve: BigUint<M>,
+    pub closed: bool,
+}
+
+/// Module selling custodial supply along a bonding curve priced in EGLD.
+/// The price of a step is the EGLD cost of `step_size` token units; every unit inside a step costs the same.
+#[multiversx_sc::module]
+pub trait BondingCurveModule {
+    /// Storage to hold the sale of each listed token
+    #[view(get_token_sale)]
+    #[storage_mapper("token_sale")]
+    fn token_sale(&self, token_identifier: &TokenIdentifier) -> SingleValueMapper<TokenSale<Self::Api>>;
+
+    /// Storage to track the tokens each buyer bought from the curve and can sell back
+    #[view(get_curve_balance)]
+    #[storage_mapper("curve_balance")]
+    fn curve_balance(
+        &self,
+        token_identifier: &TokenIdentifier,
+        buyer: &ManagedAddress,
+    ) -> SingleValueMapper<BigUint>;
+
+    /// Endpoint to buy tokens with EGLD along the curve
+    #[payable("EGLD")]
+    #[endpoint(buy)]
+    fn buy(&self, token_identifier: TokenIdentifier, min_out: BigUint) {
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().egld_value().clone_value();
+
+        require!(payment > 0, "Payment must be greater than zero");
+        require!(
+            !self.token_sale(&token_identifier).is_empty(),
+            "Token is not listed for sale"
+        );
+
+        let mut sale = self.token_sale(&token_identifier).get();
+        require!(!sale.closed, "Token sale is closed");
+
+        let (tokens_out, cost) = self.compute_buy(&sale, &payment);
+
+        require!(tokens_out > 0, "Payment too small to buy any tokens");
+        require!(tokens_out >= min_out, "Slippage exceeded");
+
+        sale.sold_amount += &tokens_out;
+        sale.reserve += &cost;
+        self.token_sale(&token_identifier).set(sale);
+        self.curve_balance(&token_identifier, &caller)
+            .update(|balance| *balance += &tokens_out);
+
+        self.send().direct_esdt(&caller, &token_identifier, 0, &tokens_out);
+
+        // Refund the part of the payment that could not be spent
+        let change = &payment - &cost;
+        if change > 0 {
+            self.tx().to(&caller).egld(&change).transfer();
+        }
+
+        self.tokens_bought_event(token_identifier, caller, cost, tokens_out);
+    }
+
+    /// Endpoint to sell tokens back along the curve for EGLD from the reserve.
+    /// Only the tokens the caller bought from the curve can be sold back.
+    #[payable("*")]
+    #[endpoint(sell)]
+    fn sell(&self, min_out: BigUint) {
+        let caller = self.blockchain().get_caller();
+        let (token_identifier, amount) = self.call_value().single_fungible_esdt();
+
+        require!(amount > 0, "Token amount must be greater than zero");
+        require!(
+            !self.token_sale(&token_identifier).is_empty(),
+            "Token is not listed for sale"
+        );
+
+        let bought_amount = self.curve_balance(&token_identifier, &caller).get();
+        require!(
+            amount <= bought_amount,
+            "Cannot sell more tokens than were bought from the curve"
+        );
+
+        let mut sale = self.token_sale(&token_identifier).get();
+        let egld_out = self.compute_sell(&sale, &amount);
+        require!(egld_out >= min_out, "Slippage exceeded");
+        require!(egld_out <= sale.reserve, "Insufficient reserve");
+
+        sale.sold_amount -= &amount;
+        sale.reserve -= &egld_out;
+        self.token_sale(&token_identifier).set(sale);
+        self.curve_balance(&token_identifier, &caller)
+            .set(&bought_amount - &amount);
+
+        if egld_out > 0 {
+            self.tx().to(&caller).egld(&egld_out).transfer();
+        }
+
+        self.tokens_sold_event(token_identifier, caller, amount, egld_out);
+    }
+
+    /// Endpoint for the issuer to close a sale and take back the tokens that are not sold.
+    /// Buyers can still sell back to the reserve; calling it again returns the tokens sold back since.
+    #[endpoint(close_token_sale)]
+    fn close_token_sale(&self, token_identifier: TokenIdentifier) {
+        let caller = self.blockchain().get_caller();
+
+        require!(
+            !self.token_sale(&token_identifier).is_empty(),
+            "Token is not listed for sale"
+        );
+
+        let mut sale = self.token_sale(&token_identifier).get();
+        require!(
+            caller == sale.issuer,
+            "Only the token issuer can close the sale"
+        );
+
+        let unsold_amount = &sale.listed_amount - &sale.sold_amount;
+        sale.listed_amount = sale.sold_amount.clone();
+        sale.closed = true;
+        self.token_sale(&token_identifier).set(sale);
+
+        if unsold_amount > 0 {
+            self.send().direct_esdt(&caller, &token_identifier, 0, &unsold_amount);
+        }
+
+        self.token_sale_closed_event(token_identifier, caller, unsold_amount);
+    }
+
+    /// Endpoint for the issuer to withdraw the EGLD reserve of a closed sale.
+    /// The reserve backs the sell-backs, so it is only released once buyers hold no curve tokens.
+    #[endpoint(withdraw_sale_reserve)]
+    fn withdraw_sale_reserve(&self, token_identifier: TokenIdentifier) {
+        let caller = self.blockchain().get_caller();
+
+        require!(
+            !self.token_sale(&token_identifier).is_empty(),
+            "Token is not listed for sale"
+        );
+
+        let mut sale = self.token_sale(&token_identifier).get();
+        require!(
+            caller == sale.issuer,
+            "Only the token issuer can withdraw the reserve"
+        );
+        require!(sale.closed, "Token sale must be closed first");
+        require!(
+            sale.sold_amount == 0,
+            "Buyers still hold tokens bought from the curve"
+        );
+        require!(sale.reserve > 0, "No reserve to withdraw");
+
+        let reserve = sale.reserve.clone();
+        sale.reserve = BigUint::zero();
+        self.token_sale(&token_identifier).set(sale);
+
+        self.tx().to(&caller).egld(&reserve).transfer();
+
+        self.sale_reserve_withdrawn_event(token_identifier, caller, reserve);
+    }
+
+    /// View endpoint returning the amount of tokens bought with an EGLD amount and the EGLD actually spent
+    #[view(get_buy_quote)]
+    fn get_buy_quote(
+        &self,
+        token_identifier: TokenIdentifier,
+        egld_amount: BigUint,
+    ) -> MultiValue2<BigUint, BigUint> {
+        require!(
+            !self.token_sale(&token_identifier).is_empty(),
+            "Token is not listed for sale"
+        );
+
+        let sale = self.token_sale(&token_identifier).get();
+        self.compute_buy(&sale, &egld_amount).into()
+    }
+
+    /// View endpoint returning the EGLD received for selling an amount of tokens
+    #[view(get_sell_quote)]
+    fn get_sell_quote(&self, token_identifier: TokenIdentifier, amount: BigUint) -> BigUint {
+        require!(
+            !self.token_sale(&token_identifier).is_empty(),
+            "Token is not listed for sale"
+        );
+
+        let sale = self.token_sale(&token_identifier).get();
+        require!(
+            amount <= sale.sold_amount,
+            "Cannot sell more tokens than were bought from the curve"
+        );
+
+        self.compute_sell(&sale, &amount)
+    }
+
+    /// Stores a new sale for a token, listed by the caller
+    fn add_token_sale(
+        &self,
+        token_identifier: TokenIdentifier,
+        kind: CurveKind,
+        amount: BigUint,
+        step_size: BigUint,
+        initial_price: BigUint,
+        price_factor: BigUint,
+    ) {
+        require!(
+            self.token_sale(&token_identifier).is_empty(),
+            "Token is already listed for sale"
+        );
+        require!(amount > 0, "Listed amount must be greater than zero");
+        require!(step_size > 0, "Step size must be greater than zero");
+        require!(initial_price > 0, "Initial price must be greater than zero");
+        if kind == CurveKind::Exponential {
+            require!(
+                price_factor <= MAX_GROWTH_BPS,
+                "Exponential growth must be at most 10000 basis points"
+            );
+            require!(
+                &amount / &step_size <= MAX_EXPONENTIAL_STEPS,
+                "Exponential curves are limited to 1000 price steps"
+            );
+        }
+
+        let issuer = self.blockchain().get_caller();
+        self.token_sale(&token_identifier).set(TokenSale {
+            issuer: issuer.clone(),
+            kind,
+            step_size,
+            initial_price,
+            price_factor,
+            listed_amount: amount.clone(),
+            sold_amount: BigUint::zero(),
+            reserve: BigUint::zero(),
+            closed: false,
+        });
+
+        self.token_listed_event(token_identifier, issuer, amount);
+    }
+
+    /// Walks the curve up from the sold amount and returns the tokens bought and the EGLD spent
+    fn compute_buy(&self, sale: &TokenSale<Self::Api>, payment: &BigUint) -> (BigUint, BigUint) {
+        let mut remaining_payment = payment.clone();
+        let mut sold = sale.sold_amount.clone();
+        let mut tokens_out = BigUint::zero();
+        let mut steps = 0u32;
+
+        while remaining_payment > 0 && sold < sale.listed_amount {
+            require!(steps < MAX_STEPS_PER_TRADE, "Trade spans too many price steps");
+            steps += 1;
+
+            let step = self.step_index(sale, &sold);
+            let price = self.step_price(sale, step);
+
+            // Units left in the current step, capped by the listed amount
+            let step_end = BigUint::from(step + 1) * &sale.step_size;
+            let mut available = &step_end - &sold;
+            let unsold = &sale.listed_amount - &sold;
+            if unsold < available {
+                available = unsold;
+            }
+
+            let available_cost = &price * &available / &sale.step_size;
+            if available_cost <= remaining_payment {
+                remaining_payment -= &available_cost;
+                tokens_out += &available;
+                sold += &available;
+            } else {
+                let units = &remaining_payment * &sale.step_size / &price;
+                if units == 0 {
+                    break;
+                }
+
+                tokens_out += &units;
+                remaining_payment = BigUint::zero();
+            }
+        }
+
+        let cost = payment - &remaining_payment;
+        (tokens_out, cost)
+    }
+
+    /// Walks the curve down from the sold amount and returns the EGLD paid for the tokens
+    fn compute_sell(&self, sale: &TokenSale<Self::Api>, amount: &BigUint) -> BigUint {
+        let mut remaining = amount.clone();
+        let mut sold = sale.sold_amount.clone();
+        let mut egld_out = BigUint::zero();
+        let mut steps = 0u32;
+
+        while remaining > 0 {
+            require!(steps < MAX_STEPS_PER_TRADE, "Trade spans too many price steps");
+            steps += 1;
+
+            // The last sold unit belongs to the step being unwound
+            let step = self.step_index(sale, &(&sold - &BigUint::from(1u32)));
+            let price = self.step_price(sale, step);
+
+            let step_start = BigUint::from(step) * &sale.step_size;
+            let mut available = &sold - &step_start;
+            if remaining < available {
+                available = remaining.clone();
+            }
+
+            egld_out += &price * &available / &sale.step_size;
+            sold -= &available;
+            remaining -= &available;
+        }
+
+        egld_out
+    }
+
+    fn step_index(&self, sale: &TokenSale<Self::Api>, sold: &BigUint) -> u64 {
+        match (sold / &sale.step_size).to_u64() {
+            Some(step) => step,
+            None => sc_panic!("Price step out of range"),
+        }
+    }
+
+    /// Computes the EGLD price of one full step at the given step index
+    fn step_price(&self, sale: &TokenSale<Self::Api>, step: u64) -> BigUint {
+        match sale.kind {
+            CurveKind::Linear => &sale.initial_price + &(&sale.price_factor * &BigUint::from(step)),
+            CurveKind::Exponential => {
+                let exponent = step as u32;
+                let numerator = (BigUint::from(BPS_DENOMINATOR) + &sale.price_factor).pow(exponent);
+                let denominator = BigUint::from(BPS_DENOMINATOR).pow(exponent);
+                &sale.initial_price * &numerator / denominator
+            }
+        }
+    }
+
+    /// Event emitted when custodial supply is listed for sale.
+    #[event("token_listed")]
+    fn token_listed_event(
+        &self,
+        #[indexed] token_identifier: TokenIdentifier,
+        #[indexed] issuer: ManagedAddress,
+        amount: BigUint,
+    );
+
+    /// Event emitted when a sale is closed and the unsold tokens are returned to the issuer.
+    #[event("token_sale_closed")]
+    fn token_sale_closed_event(
+        &self,
+        #[indexed] token_identifier: TokenIdentifier,
+        #[indexed] issuer: ManagedAddress,
+        unsold_amount: BigUint,
+    );
+
+    /// Event emitted when the issuer withdraws the reserve of a closed sale.
+    #[event("sale_reserve_withdrawn")]
+    fn sale_reserve_withdrawn_event(
+        &self,
+        #[indexed] token_identifier: TokenIdentifier,
+        #[indexed] issuer: ManagedAddress,
+        amount: BigUint,
+    );
+
+    /// Event emitted when tokens are bought from the curve.
+    #[event("tokens_bought")]
+    fn tokens_bought_event(
+        &self,
+        #[indexed] token_identifier: TokenIdentifier,
+        #[indexed] buyer: ManagedAddress,
+        #[indexed] egld_amount: BigUint,
+        token_amount: BigUint,
+    );
+
+    /// Event emitted when tokens are sold back to the curve.
+    #[event("tokens_sold")]
+    fn tokens_sold_event(
+        &self,
+        #[indexed] token_identifier: TokenIdentifier,
+        #[indexed] seller: ManagedAddress,
+        #[indexed] token_amount: BigUint,
+        egld_amount: BigUint,
+    );
+}
@@ -1,9 +1,49 @@
 #![no_std]
 
+use multiversx_sc::derive_imports::*;
 use multiversx_sc::imports::*;
 
+pub mod airdrop;
+pub mod bonding_curve;
+pub mod role_management;
+pub mod vesting;
+
+use bonding_curve::CurveKind;
+use vesting::VestingKind;
+
+/// Maximum number of decimals accepted by the ESDT system smart contract
+const MAX_DECIMALS: usize = 18;
+
+/// Number of random characters in a generated token name
+const RANDOM_NAME_CHARS: usize = 8;
+
+/// Number of base-36 characters encoding the name counter in a generated token name
+const COUNTER_NAME_CHARS: u32 = 8;
+
+/// Kind of collection that can be issued besides fungible tokens
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionType {
+    NonFungible,
+    SemiFungible,
+    Meta,
+}
+
+/// Payment recorded before an issuance async call, refunded if the issuance fails
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct PendingIssuance<M: ManagedTypeApi> {
+    pub caller: ManagedAddress<M>,
+    pub payment: BigUint<M>,
+}
+
 #[multiversx_sc::contract]
-pub trait IssueTokenSnowSc {
+pub trait IssueTokenSnowSc:
+    role_management::RoleManagementModule
+    + vesting::VestingModule
+    + airdrop::AirdropModule
+    + bonding_curve::BondingCurveModule
+{
     #[init]
     fn init(&self) {}
 
@@ -15,6 +55,33 @@ pub trait IssueTokenSnowSc {
         (ManagedAddress<Self::Api>, TokenIdentifier<Self::Api>),
         BigUint<Self::Api>,
     >;
+
+    /// Counter of generated token names, keeps generated names unique
+    #[storage_mapper("token_name_counter")]
+    fn token_name_counter(&self) -> SingleValueMapper<u64>;
+
+    /// Storage to track the issuances waiting for the ESDT system smart contract
+    #[storage_mapper("pending_issuance")]
+    fn pending_issuance(&self, issuance_id: u64) -> SingleValueMapper<PendingIssuance<Self::Api>>;
+
+    /// Storage to hold the id of the last pending issuance
+    #[storage_mapper("last_issuance_id")]
+    fn last_issuance_id(&self) -> SingleValueMapper<u64>;
+
+    /// Storage to track the identifier of each collection issued through the contract, by ticker
+    #[view(get_collection)]
+    #[storage_mapper("collection_by_ticker")]
+    fn collection_by_ticker(&self, token_ticker: &ManagedBuffer) -> SingleValueMapper<TokenIdentifier>;
+
+    /// Storage to track the tickers of the collections waiting for the ESDT system smart contract
+    #[storage_mapper("collection_ticker_pending")]
+    fn collection_ticker_pending(&self, token_ticker: &ManagedBuffer) -> SingleValueMapper<bool>;
+
+    /// Storage to track the type of each collection issued through the contract
+    #[view(get_collection_type)]
+    #[storage_mapper("collection_type")]
+    fn collection_type(&self, token_identifier: &TokenIdentifier) -> SingleValueMapper<CollectionType>;
+
     /// Event emitted when a token is successfully issued.
     #[event("token_issued")]
     fn token_issued_event(
@@ -24,6 +91,24 @@ pub trait IssueTokenSnowSc {
         initial_supply: BigUint,
     );
 
+    /// Event emitted when a token issuance fails and the payment is refunded.
+    #[event("token_issue_failed")]
+    fn token_issue_failed_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] refunded_amount: BigUint,
+        error_message: ManagedBuffer,
+    );
+
+    /// Event emitted when a collection is successfully issued.
+    #[event("collection_issued")]
+    fn collection_issued_event(
+        &self,
+        #[indexed] token_identifier: TokenIdentifier,
+        #[indexed] collection_type: CollectionType,
+        #[indexed] issuer: ManagedAddress,
+    );
+
     /// Event emitted when tokens are claimed.
     #[event("tokens_claimed")]
     fn tokens_claimed_event(
@@ -60,7 +145,9 @@ pub trait IssueTokenSnowSc {
     fn issue_token_snow(
         &self,
         mut token_name: ManagedBuffer,
+        token_ticker: ManagedBuffer,
         initial_supply: BigUint<Self::Api>,
+        num_decimals: usize,
         can_freeze: bool,
         can_wipe: bool,
         can_pause: bool,
@@ -77,7 +164,7 @@ pub trait IssueTokenSnowSc {
         require!(*payment >= issue_cost, "Minimum fee is 0.05 EGLD");
 
         if token_name.is_empty() {
-            token_name = self.generate_random_token_name();
+            token_name = self.generate_random_token_name(&self.blockchain().get_caller());
         }
 
         require!(
@@ -85,10 +172,14 @@ pub trait IssueTokenSnowSc {
             "Initial supply must be greater than 0"
         );
 
-        let token_ticker = ManagedBuffer::from("SNOW");
-        self.emit_log_message("Token ticker set to SNOW");
+        // Validate everything before the async call so the issue fee is not lost
+        self.validate_token_name(&token_name);
+        self.validate_token_ticker(&token_ticker);
+        require!(
+            num_decimals <= MAX_DECIMALS,
+            "Number of decimals must be between 0 and 18"
+        );
 
-        let num_decimals = self.get_decimals();
         let adjusted_supply = initial_supply.clone() * BigUint::from(10u64).pow(num_decimals as u32);
 
         let properties = FungibleTokenProperties {
@@ -103,6 +194,10 @@ pub trait IssueTokenSnowSc {
             can_add_special_roles,
         };
 
+        // Record the full payment so it can be refunded if the issuance fails
+        let issuance_id =
+            self.record_pending_issuance(self.blockchain().get_caller(), payment.clone_value());
+
         // Call the ESDT system smart contract to issue the token
         self.send()
             .esdt_system_sc_proxy()
@@ -113,9 +208,7 @@ pub trait IssueTokenSnowSc {
                 &adjusted_supply,
                 properties,
             )
-            .with_callback(self.callbacks().esdt_issue_callback(
-                self.blockchain().get_caller(),
-            ))
+            .with_callback(self.callbacks().esdt_issue_callback(issuance_id, token_name))
             .async_call_and_exit();
     }
 
@@ -123,15 +216,21 @@ pub trait IssueTokenSnowSc {
     #[callback]
     fn esdt_issue_callback(
         &self,
-        caller: ManagedAddress,
+        issuance_id: u64,
+        token_name: ManagedBuffer,
         #[call_result] result: ManagedAsyncCallResult<()>,
     ) {
+        let pending = self.pending_issuance(issuance_id).take();
+        let caller = pending.caller.clone();
         let (token_identifier, returned_tokens) = self.call_value().egld_or_single_fungible_esdt();
         match result {
             ManagedAsyncCallResult::Ok(()) => {
                 self.emit_log_message("Token issuance successful");
         
                 let unwrapped_identifier = token_identifier.unwrap_esdt();
+
+                // Record the issuer so they can manage the token afterwards
+                self.token_issuer(&unwrapped_identifier).set(&caller);
                 
                 // Update the user's balance in storage
                 self.update_user_balance(caller.clone(), unwrapped_identifier.clone(), returned_tokens.clone());
@@ -139,17 +238,15 @@ pub trait IssueTokenSnowSc {
                 // Emit event for successful issuance
                 self.token_issued_event(
                     unwrapped_identifier, // Use the unwrapped identifier here
-                    ManagedBuffer::from("Token Name"),
+                    token_name,
                     returned_tokens,
                 );
             }
-            ManagedAsyncCallResult::Err(_err) => {
+            ManagedAsyncCallResult::Err(err) => {
                 self.emit_log_message("Token issuance failed");
-        
-                // Refund the caller if necessary
-                if token_identifier.is_egld() && returned_tokens > 0 {
-                    self.tx().to(&caller).egld(&returned_tokens).transfer();
-                }
+
+                // Refund the full amount paid by the caller
+                self.refund_failed_issuance(pending, err.err_msg);
             }
         }
     }
@@ -160,7 +257,9 @@ pub trait IssueTokenSnowSc {
     fn issue_token_snow_and_transfer(
         &self,
         mut token_name: ManagedBuffer,
+        token_ticker: ManagedBuffer,
         initial_supply: BigUint<Self::Api>,
+        num_decimals: usize,
         can_freeze: bool,
         can_wipe: bool,
         can_pause: bool,
@@ -177,7 +276,7 @@ pub trait IssueTokenSnowSc {
         require!(*payment >= issue_cost, "Minimum fee is 0.05 EGLD");
 
         if token_name.is_empty() {
-            token_name = self.generate_random_token_name();
+            token_name = self.generate_random_token_name(&self.blockchain().get_caller());
         }
 
         require!(
@@ -185,10 +284,14 @@ pub trait IssueTokenSnowSc {
             "Initial supply must be greater than 0"
         );
 
-        let token_ticker = ManagedBuffer::from("SNOW");
-        self.emit_log_message("Token ticker set to SNOW");
+        // Validate everything before the async call so the issue fee is not lost
+        self.validate_token_name(&token_name);
+        self.validate_token_ticker(&token_ticker);
+        require!(
+            num_decimals <= MAX_DECIMALS,
+            "Number of decimals must be between 0 and 18"
+        );
 
-        let num_decimals = self.get_decimals();
         let adjusted_supply = initial_supply.clone() * BigUint::from(10u64).pow(num_decimals as u32);
 
         let properties = FungibleTokenProperties {
@@ -203,6 +306,10 @@ pub trait IssueTokenSnowSc {
             can_add_special_roles,
         };
 
+        // Record the full payment so it can be refunded if the issuance fails
+        let issuance_id =
+            self.record_pending_issuance(self.blockchain().get_caller(), payment.clone_value());
+
         // Call the ESDT system smart contract to issue the token
         self.send()
             .esdt_system_sc_proxy()
@@ -213,9 +320,7 @@ pub trait IssueTokenSnowSc {
                 &adjusted_supply,
                 properties,
             )
-            .with_callback(self.callbacks().esdt_issue_and_transfer_callback(
-                self.blockchain().get_caller(),
-            ))
+            .with_callback(self.callbacks().esdt_issue_and_transfer_callback(issuance_id, token_name))
             .async_call_and_exit();
     }
 
@@ -223,15 +328,21 @@ pub trait IssueTokenSnowSc {
     #[callback]
     fn esdt_issue_and_transfer_callback(
         &self,
-        caller: ManagedAddress,
+        issuance_id: u64,
+        token_name: ManagedBuffer,
         #[call_result] result: ManagedAsyncCallResult<()>,
     ) {
+        let pending = self.pending_issuance(issuance_id).take();
+        let caller = pending.caller.clone();
         let (token_identifier, returned_tokens) = self.call_value().egld_or_single_fungible_esdt();
         match result {
             ManagedAsyncCallResult::Ok(()) => {
                 self.emit_log_message("Token issuance successful");
         
                 let unwrapped_identifier = token_identifier.unwrap_esdt();
+
+                // Record the issuer so they can manage the token afterwards
+                self.token_issuer(&unwrapped_identifier).set(&caller);
         
                 // Transfer tokens to the caller
                 self.send()
@@ -251,22 +362,167 @@ pub trait IssueTokenSnowSc {
                 // Emit event for successful issuance
                 self.token_issued_event(
                     unwrapped_identifier, // Use the unwrapped identifier here
-                    ManagedBuffer::from("Token Name"),
+                    token_name,
                     returned_tokens,
                 );
             }
-            ManagedAsyncCallResult::Err(_err) => {
+            ManagedAsyncCallResult::Err(err) => {
                 self.emit_log_message("Token issuance failed");
-        
-                // Refund the caller if necessary
-                if token_identifier.is_egld() && returned_tokens > 0 {
-                    self.tx().to(&caller).egld(&returned_tokens).transfer();
-                }
+
+                // Refund the full amount paid by the caller
+                self.refund_failed_issuance(pending, err.err_msg);
+            }
+        }
+    }
+
+    /// Endpoint to issue an NFT, SFT or Meta-ESDT collection.
+    /// `num_decimals` is only used for Meta-ESDT collections and must be 0 otherwise.
+    #[payable("EGLD")]
+    #[endpoint(issue_collection)]
+    fn issue_collection(
+        &self,
+        collection_type: CollectionType,
+        token_name: ManagedBuffer,
+        token_ticker: ManagedBuffer,
+        num_decimals: usize,
+        can_freeze: bool,
+        can_wipe: bool,
+        can_pause: bool,
+        can_transfer_create_role: bool,
+        can_change_owner: bool,
+        can_upgrade: bool,
+        can_add_special_roles: bool,
+    ) {
+        self.emit_log_message("Starting collection issuance process");
+
+        let payment = self.call_value().egld_value();
+        let issue_cost = BigUint::from(50_000_000_000_000_000u64); // 0.05 EGLD
+        require!(*payment >= issue_cost, "Minimum fee is 0.05 EGLD");
+
+        // Validate everything before the async call so the issue fee is not lost
+        self.validate_token_name(&token_name);
+        self.validate_token_ticker(&token_ticker);
+        if collection_type == CollectionType::Meta {
+            require!(
+                num_decimals <= MAX_DECIMALS,
+                "Number of decimals must be between 0 and 18"
+            );
+        } else {
+            require!(
+                num_decimals == 0,
+                "Only Meta-ESDT collections can have decimals"
+            );
+        }
+        require!(
+            self.collection_by_ticker(&token_ticker).is_empty(),
+            "A collection with this ticker was already issued"
+        );
+        require!(
+            !self.collection_ticker_pending(&token_ticker).get(),
+            "A collection with this ticker is already being issued"
+        );
+
+        // Record the full payment so it can be refunded if the issuance fails
+        let issuance_id =
+            self.record_pending_issuance(self.blockchain().get_caller(), payment.clone_value());
+        // Reserve the ticker until the callback, so a second issuance cannot race the first one
+        self.collection_ticker_pending(&token_ticker).set(true);
+        let callback = self.callbacks().esdt_issue_collection_callback(
+            issuance_id,
+            collection_type,
+            token_ticker.clone(),
+        );
+
+        // Call the ESDT system smart contract to issue the collection
+        match collection_type {
+            CollectionType::NonFungible => {
+                let properties = NonFungibleTokenProperties {
+                    can_freeze,
+                    can_wipe,
+                    can_pause,
+                    can_transfer_create_role,
+                    can_change_owner,
+                    can_upgrade,
+                    can_add_special_roles,
+                };
+
+                self.send()
+                    .esdt_system_sc_proxy()
+                    .issue_non_fungible(issue_cost, &token_name, &token_ticker, properties)
+                    .with_callback(callback)
+                    .async_call_and_exit();
+            }
+            CollectionType::SemiFungible => {
+                let properties = SemiFungibleTokenProperties {
+                    can_freeze,
+                    can_wipe,
+                    can_pause,
+                    can_transfer_create_role,
+                    can_change_owner,
+                    can_upgrade,
+                    can_add_special_roles,
+                };
+
+                self.send()
+                    .esdt_system_sc_proxy()
+                    .issue_semi_fungible(issue_cost, &token_name, &token_ticker, properties)
+                    .with_callback(callback)
+                    .async_call_and_exit();
+            }
+            CollectionType::Meta => {
+                let properties = MetaTokenProperties {
+                    num_decimals,
+                    can_freeze,
+                    can_wipe,
+                    can_pause,
+                    can_transfer_create_role,
+                    can_change_owner,
+                    can_upgrade,
+                    can_add_special_roles,
+                };
+
+                self.send()
+                    .esdt_system_sc_proxy()
+                    .register_meta_esdt(issue_cost, &token_name, &token_ticker, properties)
+                    .with_callback(callback)
+                    .async_call_and_exit();
+            }
+        }
+    }
+
+    /// Callback for collection issuance
+    #[callback]
+    fn esdt_issue_collection_callback(
+        &self,
+        issuance_id: u64,
+        collection_type: CollectionType,
+        token_ticker: ManagedBuffer,
+        #[call_result] result: ManagedAsyncCallResult<TokenIdentifier>,
+    ) {
+        let pending = self.pending_issuance(issuance_id).take();
+        self.collection_ticker_pending(&token_ticker).clear();
+        match result {
+            ManagedAsyncCallResult::Ok(token_identifier) => {
+                self.emit_log_message("Collection issuance successful");
+
+                // Record the identifier and the issuer so the collection can be managed afterwards
+                self.collection_by_ticker(&token_ticker).set(&token_identifier);
+                self.collection_type(&token_identifier).set(collection_type);
+                self.token_issuer(&token_identifier).set(&pending.caller);
+
+                self.collection_issued_event(token_identifier, collection_type, pending.caller);
+            }
+            ManagedAsyncCallResult::Err(err) => {
+                self.emit_log_message("Collection issuance failed");
+
+                // Refund the full amount paid by the caller
+                self.refund_failed_issuance(pending, err.err_msg);
             }
         }
     }
 
     /// Endpoint to claim tokens.
+    /// Releases the custodial balance of the caller together with the vested part of their vesting schedules.
     #[endpoint(claim_tokens)]
     fn claim_tokens(&self, token_identifier: TokenIdentifier<Self::Api>) {
         let caller = self.blockchain().get_caller();
@@ -277,21 +533,85 @@ pub trait IssueTokenSnowSc {
             .issued_tokens()
             .get(&key)
             .unwrap_or_else(BigUint::zero);
+        let vested_amount = self.release_vested_tokens(&caller, &token_identifier);
+        let claim_amount = &user_balance + &vested_amount;
 
         require!(
-            user_balance > BigUint::zero(),
+            claim_amount > BigUint::zero(),
             "No tokens available to claim for this user"
         );
 
         // Transfer tokens to the caller
         self.send()
-            .direct_esdt(&caller, &token_identifier, 0, &user_balance);
+            .direct_esdt(&caller, &token_identifier, 0, &claim_amount);
 
         // Update storage: remove claimed tokens
         self.issued_tokens().remove(&key);
 
         // Emit event
-        self.tokens_claimed_event(token_identifier, caller, user_balance);
+        self.tokens_claimed_event(token_identifier, caller, claim_amount);
+    }
+
+    /// Endpoint to lock part of the caller's custodial balance into a vesting schedule for a beneficiary.
+    #[endpoint(create_vesting_schedule)]
+    fn create_vesting_schedule(
+        &self,
+        token_identifier: TokenIdentifier<Self::Api>,
+        beneficiary: ManagedAddress,
+        amount: BigUint<Self::Api>,
+        kind: VestingKind,
+        start_timestamp: u64,
+        end_timestamp: u64,
+    ) {
+        self.require_token_issuer(&token_identifier);
+
+        // Move the tokens from the custodial balance into the schedule
+        self.debit_custodial_balance(
+            &token_identifier,
+            &amount,
+            "Insufficient custodial balance for vesting",
+        );
+
+        self.add_vesting_schedule(
+            token_identifier,
+            beneficiary,
+            kind,
+            amount,
+            start_timestamp,
+            end_timestamp,
+        );
+    }
+
+    /// Endpoint to list part of the caller's custodial balance for sale on a bonding curve.
+    /// `price_factor` is the EGLD price increase per step for linear curves and the growth
+    /// per step in basis points for exponential curves.
+    #[endpoint(list_token_for_sale)]
+    fn list_token_for_sale(
+        &self,
+        token_identifier: TokenIdentifier<Self::Api>,
+        amount: BigUint<Self::Api>,
+        kind: CurveKind,
+        step_size: BigUint<Self::Api>,
+        initial_price: BigUint<Self::Api>,
+        price_factor: BigUint<Self::Api>,
+    ) {
+        self.require_token_issuer(&token_identifier);
+
+        // Move the tokens from the custodial balance into the sale
+        self.debit_custodial_balance(
+            &token_identifier,
+            &amount,
+            "Insufficient custodial balance for sale",
+        );
+
+        self.add_token_sale(
+            token_identifier,
+            kind,
+            amount,
+            step_size,
+            initial_price,
+            price_factor,
+        );
     }
 
     /// Single endpoint to handle token transfer and burning.
@@ -343,6 +663,59 @@ pub trait IssueTokenSnowSc {
         result
     }
 
+    /// View endpoint to preview the token name that would be generated for a caller.
+    /// The preview is only valid within the current block, as it depends on the block random seed.
+    #[view(get_next_token_name)]
+    fn get_next_token_name(&self, caller: ManagedAddress) -> ManagedBuffer {
+        self.compute_token_name(&caller, self.token_name_counter().get())
+    }
+
+    /// Stores the caller's payment for an issuance and returns the issuance id.
+    fn record_pending_issuance(&self, caller: ManagedAddress, payment: BigUint) -> u64 {
+        let issuance_id = self.last_issuance_id().get() + 1;
+        self.last_issuance_id().set(issuance_id);
+
+        self.pending_issuance(issuance_id)
+            .set(PendingIssuance { caller, payment });
+
+        issuance_id
+    }
+
+    /// Refunds the exact payment of a failed issuance and emits the failure event.
+    fn refund_failed_issuance(
+        &self,
+        pending: PendingIssuance<Self::Api>,
+        error_message: ManagedBuffer,
+    ) {
+        if pending.payment > 0 {
+            self.tx().to(&pending.caller).egld(&pending.payment).transfer();
+        }
+
+        self.token_issue_failed_event(pending.caller, pending.payment, error_message);
+    }
+
+    /// Removes an amount from the caller's custodial balance, failing with `err` if it is too low.
+    fn debit_custodial_balance(
+        &self,
+        token_identifier: &TokenIdentifier,
+        amount: &BigUint,
+        err: &str,
+    ) {
+        let key = (self.blockchain().get_caller(), token_identifier.clone());
+        let user_balance = self
+            .issued_tokens()
+            .get(&key)
+            .unwrap_or_else(BigUint::zero);
+
+        require!(&user_balance >= amount, err);
+
+        if &user_balance == amount {
+            self.issued_tokens().remove(&key);
+        } else {
+            self.issued_tokens().insert(key, &user_balance - amount);
+        }
+    }
+
     fn update_user_balance(
         &self,
         user_address: ManagedAddress<Self::Api>,
@@ -357,33 +730,80 @@ pub trait IssueTokenSnowSc {
         }
     }
 
-    /// Returns the fixed number of decimals for tokens.
-    fn get_decimals(&self) -> usize {
-        8
+    /// Validates a token name against the ESDT rules: 3-20 alphanumeric characters.
+    fn validate_token_name(&self, token_name: &ManagedBuffer) {
+        let name_len = token_name.len();
+        require!(
+            (3..=20).contains(&name_len),
+            "Token name must be between 3 and 20 characters long"
+        );
+
+        let name_bytes = token_name.to_boxed_bytes();
+        require!(
+            name_bytes.as_ref().iter().all(|c| c.is_ascii_alphanumeric()),
+            "Token name must contain only alphanumeric characters"
+        );
+    }
+
+    /// Validates a token ticker against the ESDT rules: 3-10 uppercase alphanumeric characters.
+    fn validate_token_ticker(&self, token_ticker: &ManagedBuffer) {
+        let ticker_len = token_ticker.len();
+        require!(
+            (3..=10).contains(&ticker_len),
+            "Token ticker must be between 3 and 10 characters long"
+        );
+
+        let ticker_bytes = token_ticker.to_boxed_bytes();
+        require!(
+            ticker_bytes
+                .as_ref()
+                .iter()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()),
+            "Token ticker must contain only uppercase alphanumeric characters"
+        );
+    }
+
+    /// Generates a random token name for the caller and advances the name counter.
+    fn generate_random_token_name(&self, caller: &ManagedAddress) -> ManagedBuffer<Self::Api> {
+        let counter = self.token_name_counter().get();
+        self.token_name_counter().set(counter + 1);
+
+        self.compute_token_name(caller, counter)
     }
 
-    /// Generates a random token name.
-    fn generate_random_token_name(&self) -> ManagedBuffer<Self::Api> {
-        let mut name = ManagedBuffer::new(); // Default constructor
-        let block_nonce = self.blockchain().get_block_nonce();
-        let block_timestamp = self.blockchain().get_block_timestamp();
+    /// Builds a token name from the block random seed, the caller and the name counter.
+    /// The first characters are derived from a keccak256 hash of the inputs, while the
+    /// last characters encode the counter, so names never repeat within the contract.
+    fn compute_token_name(&self, caller: &ManagedAddress, counter: u64) -> ManagedBuffer<Self::Api> {
+        let mut seed_data = ManagedBuffer::new();
+        seed_data.append(self.blockchain().get_block_random_seed().as_managed_buffer());
+        seed_data.append(caller.as_managed_buffer());
+        seed_data.append_bytes(&counter.to_be_bytes());
 
-        let random_seed = block_nonce ^ block_timestamp as u64;
+        let hash_bytes = self.crypto().keccak256(&seed_data).to_byte_array();
 
-        for i in 0..8 {
-            let char_index = ((random_seed >> (i * 5)) & 0x1F) % 36;
-            let char = match char_index {
-                0..=9 => b'0' + char_index as u8,
-                10..=35 => b'A' + (char_index - 10) as u8,
-                _ => unreachable!(),
-            };
+        let mut name = ManagedBuffer::new();
+        for byte in hash_bytes.iter().take(RANDOM_NAME_CHARS) {
+            name.append_bytes(&[self.to_base36_char(*byte as u64 % 36)]);
+        }
 
-            name.append_bytes(&[char]);
+        for i in (0..COUNTER_NAME_CHARS).rev() {
+            let digit = (counter / 36u64.pow(i)) % 36;
+            name.append_bytes(&[self.to_base36_char(digit)]);
         }
 
         name
     }
 
+    /// Maps a value between 0 and 35 to an uppercase alphanumeric character.
+    fn to_base36_char(&self, value: u64) -> u8 {
+        match value {
+            0..=9 => b'0' + value as u8,
+            10..=35 => b'A' + (value - 10) as u8,
+            _ => unreachable!(),
+        }
+    }
+
     /// Emit a log message event.
     fn emit_log_message(&self, message: &str) {
         let log_buffer = ManagedBuffer::from(message);
@@ -0,0 +1,365 @@
+use multiversx_sc::imports::*;
+
+/// Module exposing the ESDT management capabilities of the issued tokens to their issuers.
+#[multiversx_sc::module]
+pub trait RoleManagementModule {
+    /// Storage to track the issuer of each token issued through the contract
+    #[view(get_token_issuer)]
+    #[storage_mapper("token_issuer")]
+    fn token_issuer(&self, token_identifier: &TokenIdentifier) -> SingleValueMapper<ManagedAddress>;
+
+    /// Endpoint to set special roles for an address
+    #[endpoint(set_special_roles)]
+    fn set_special_roles(
+        &self,
+        token_identifier: TokenIdentifier,
+        address: ManagedAddress,
+        roles: MultiValueEncoded<EsdtLocalRole>,
+    ) {
+        self.require_token_issuer(&token_identifier);
+
+        let roles_vec: ManagedVec<EsdtLocalRole> = roles.to_vec();
+        require!(!roles_vec.is_empty(), "At least one role must be provided");
+
+        self.send()
+            .esdt_system_sc_proxy()
+            .set_special_roles(&address, &token_identifier, roles_vec.iter())
+            .with_callback(self.callbacks().set_special_roles_callback(
+                token_identifier,
+                address,
+                roles_vec,
+            ))
+            .async_call_and_exit();
+    }
+
+    /// Callback for setting special roles
+    #[callback]
+    fn set_special_roles_callback(
+        &self,
+        token_identifier: TokenIdentifier,
+        address: ManagedAddress,
+        roles: ManagedVec<EsdtLocalRole>,
+        #[call_result] result: ManagedAsyncCallResult<()>,
+    ) {
+        match result {
+            ManagedAsyncCallResult::Ok(()) => {
+                self.special_roles_set_event(token_identifier, address, roles);
+            }
+            ManagedAsyncCallResult::Err(err) => {
+                self.token_management_failed_event(
+                    token_identifier,
+                    ManagedBuffer::from("set_special_roles"),
+                    err.err_msg,
+                );
+            }
+        }
+    }
+
+    /// Endpoint to let a contract create, update and burn the NFTs of a collection
+    #[endpoint(set_collection_roles)]
+    fn set_collection_roles(&self, token_identifier: TokenIdentifier, address: ManagedAddress) {
+        self.require_token_issuer(&token_identifier);
+
+        let mut roles_vec = ManagedVec::new();
+        roles_vec.push(EsdtLocalRole::NftCreate);
+        roles_vec.push(EsdtLocalRole::NftUpdateAttributes);
+        roles_vec.push(EsdtLocalRole::NftBurn);
+
+        self.send()
+            .esdt_system_sc_proxy()
+            .set_special_roles(&address, &token_identifier, roles_vec.iter())
+            .with_callback(self.callbacks().set_special_roles_callback(
+                token_identifier,
+                address,
+                roles_vec,
+            ))
+            .async_call_and_exit();
+    }
+
+    /// Endpoint to unset special roles for an address
+    #[endpoint(unset_special_roles)]
+    fn unset_special_roles(
+        &self,
+        token_identifier: TokenIdentifier,
+        address: ManagedAddress,
+        roles: MultiValueEncoded<EsdtLocalRole>,
+    ) {
+        self.require_token_issuer(&token_identifier);
+
+        let roles_vec: ManagedVec<EsdtLocalRole> = roles.to_vec();
+        require!(!roles_vec.is_empty(), "At least one role must be provided");
+
+        self.send()
+            .esdt_system_sc_proxy()
+            .unset_special_roles(&address, &token_identifier, roles_vec.iter())
+            .with_callback(self.callbacks().unset_special_roles_callback(
+                token_identifier,
+                address,
+                roles_vec,
+            ))
+            .async_call_and_exit();
+    }
+
+    /// Callback for unsetting special roles
+    #[callback]
+    fn unset_special_roles_callback(
+        &self,
+        token_identifier: TokenIdentifier,
+        address: ManagedAddress,
+        roles: ManagedVec<EsdtLocalRole>,
+        #[call_result] result: ManagedAsyncCallResult<()>,
+    ) {
+        match result {
+            ManagedAsyncCallResult::Ok(()) => {
+                self.special_roles_unset_event(token_identifier, address, roles);
+            }
+            ManagedAsyncCallResult::Err(err) => {
+                self.token_management_failed_event(
+                    token_identifier,
+                    ManagedBuffer::from("unset_special_roles"),
+                    err.err_msg,
+                );
+            }
+        }
+    }
+
+    /// Endpoint to freeze the token balance of an account
+    #[endpoint(freeze_account)]
+    fn freeze_account(&self, token_identifier: TokenIdentifier, address: ManagedAddress) {
+        self.require_token_issuer(&token_identifier);
+
+        self.send()
+            .esdt_system_sc_proxy()
+            .freeze(&token_identifier, &address)
+            .with_callback(self.callbacks().freeze_callback(token_identifier, address))
+            .async_call_and_exit();
+    }
+
+    /// Callback for freezing an account
+    #[callback]
+    fn freeze_callback(
+        &self,
+        token_identifier: TokenIdentifier,
+        address: ManagedAddress,
+        #[call_result] result: ManagedAsyncCallResult<()>,
+    ) {
+        match result {
+            ManagedAsyncCallResult::Ok(()) => {
+                self.account_frozen_event(token_identifier, address);
+            }
+            ManagedAsyncCallResult::Err(err) => {
+                self.token_management_failed_event(
+                    token_identifier,
+                    ManagedBuffer::from("freeze_account"),
+                    err.err_msg,
+                );
+            }
+        }
+    }
+
+    /// Endpoint to unfreeze the token balance of an account
+    #[endpoint(unfreeze_account)]
+    fn unfreeze_account(&self, token_identifier: TokenIdentifier, address: ManagedAddress) {
+        self.require_token_issuer(&token_identifier);
+
+        self.send()
+            .esdt_system_sc_proxy()
+            .unfreeze(&token_identifier, &address)
+            .with_callback(self.callbacks().unfreeze_callback(token_identifier, address))
+            .async_call_and_exit();
+    }
+
+    /// Callback for unfreezing an account
+    #[callback]
+    fn unfreeze_callback(
+        &self,
+        token_identifier: TokenIdentifier,
+        address: ManagedAddress,
+        #[call_result] result: ManagedAsyncCallResult<()>,
+    ) {
+        match result {
+            ManagedAsyncCallResult::Ok(()) => {
+                self.account_unfrozen_event(token_identifier, address);
+            }
+            ManagedAsyncCallResult::Err(err) => {
+                self.token_management_failed_event(
+                    token_identifier,
+                    ManagedBuffer::from("unfreeze_account"),
+                    err.err_msg,
+                );
+            }
+        }
+    }
+
+    /// Endpoint to wipe the token balance of a frozen account
+    #[endpoint(wipe_account)]
+    fn wipe_account(&self, token_identifier: TokenIdentifier, address: ManagedAddress) {
+        self.require_token_issuer(&token_identifier);
+
+        self.send()
+            .esdt_system_sc_proxy()
+            .wipe(&token_identifier, &address)
+            .with_callback(self.callbacks().wipe_callback(token_identifier, address))
+            .async_call_and_exit();
+    }
+
+    /// Callback for wiping an account
+    #[callback]
+    fn wipe_callback(
+        &self,
+        token_identifier: TokenIdentifier,
+        address: ManagedAddress,
+        #[call_result] result: ManagedAsyncCallResult<()>,
+    ) {
+        match result {
+            ManagedAsyncCallResult::Ok(()) => {
+                self.account_wiped_event(token_identifier, address);
+            }
+            ManagedAsyncCallResult::Err(err) => {
+                self.token_management_failed_event(
+                    token_identifier,
+                    ManagedBuffer::from("wipe_account"),
+                    err.err_msg,
+                );
+            }
+        }
+    }
+
+    /// Endpoint to pause all transfers of a token
+    #[endpoint(pause_token)]
+    fn pause_token(&self, token_identifier: TokenIdentifier) {
+        self.require_token_issuer(&token_identifier);
+
+        self.send()
+            .esdt_system_sc_proxy()
+            .pause(&token_identifier)
+            .with_callback(self.callbacks().pause_callback(token_identifier))
+            .async_call_and_exit();
+    }
+
+    /// Callback for pausing a token
+    #[callback]
+    fn pause_callback(
+        &self,
+        token_identifier: TokenIdentifier,
+        #[call_result] result: ManagedAsyncCallResult<()>,
+    ) {
+        match result {
+            ManagedAsyncCallResult::Ok(()) => {
+                self.token_paused_event(token_identifier);
+            }
+            ManagedAsyncCallResult::Err(err) => {
+                self.token_management_failed_event(
+                    token_identifier,
+                    ManagedBuffer::from("pause_token"),
+                    err.err_msg,
+                );
+            }
+        }
+    }
+
+    /// Endpoint to resume the transfers of a paused token
+    #[endpoint(unpause_token)]
+    fn unpause_token(&self, token_identifier: TokenIdentifier) {
+        self.require_token_issuer(&token_identifier);
+
+        self.send()
+            .esdt_system_sc_proxy()
+            .unpause(&token_identifier)
+            .with_callback(self.callbacks().unpause_callback(token_identifier))
+            .async_call_and_exit();
+    }
+
+    /// Callback for unpausing a token
+    #[callback]
+    fn unpause_callback(
+        &self,
+        token_identifier: TokenIdentifier,
+        #[call_result] result: ManagedAsyncCallResult<()>,
+    ) {
+        match result {
+            ManagedAsyncCallResult::Ok(()) => {
+                self.token_unpaused_event(token_identifier);
+            }
+            ManagedAsyncCallResult::Err(err) => {
+                self.token_management_failed_event(
+                    token_identifier,
+                    ManagedBuffer::from("unpause_token"),
+                    err.err_msg,
+                );
+            }
+        }
+    }
+
+    /// Ensures the caller is the address that issued the token through this contract
+    fn require_token_issuer(&self, token_identifier: &TokenIdentifier) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            !self.token_issuer(token_identifier).is_empty(),
+            "Token was not issued through this contract"
+        );
+        require!(
+            self.token_issuer(token_identifier).get() == caller,
+            "Only the token issuer can manage the token"
+        );
+    }
+
+    /// Event emitted when special roles are set for an address.
+    #[event("special_roles_set")]
+    fn special_roles_set_event(
+        &self,
+        #[indexed] token_identifier: TokenIdentifier,
+        #[indexed] address: ManagedAddress,
+        roles: ManagedVec<EsdtLocalRole>,
+    );
+
+    /// Event emitted when special roles are unset for an address.
+    #[event("special_roles_unset")]
+    fn special_roles_unset_event(
+        &self,
+        #[indexed] token_identifier: TokenIdentifier,
+        #[indexed] address: ManagedAddress,
+        roles: ManagedVec<EsdtLocalRole>,
+    );
+
+    /// Event emitted when an account is frozen.
+    #[event("account_frozen")]
+    fn account_frozen_event(
+        &self,
+        #[indexed] token_identifier: TokenIdentifier,
+        #[indexed] address: ManagedAddress,
+    );
+
+    /// Event emitted when an account is unfrozen.
+    #[event("account_unfrozen")]
+    fn account_unfrozen_event(
+        &self,
+        #[indexed] token_identifier: TokenIdentifier,
+        #[indexed] address: ManagedAddress,
+    );
+
+    /// Event emitted when the token balance of an account is wiped.
+    #[event("account_wiped")]
+    fn account_wiped_event(
+        &self,
+        #[indexed] token_identifier: TokenIdentifier,
+        #[indexed] address: ManagedAddress,
+    );
+
+    /// Event emitted when a token is paused.
+    #[event("token_paused")]
+    fn token_paused_event(&self, #[indexed] token_identifier: TokenIdentifier);
+
+    /// Event emitted when a token is unpaused.
+    #[event("token_unpaused")]
+    fn token_unpaused_event(&self, #[indexed] token_identifier: TokenIdentifier);
+
+    /// Event emitted when a token management call to the ESDT system smart contract fails.
+    #[event("token_management_failed")]
+    fn token_management_failed_event(
+        &self,
+        #[indexed] token_identifier: TokenIdentifier,
+        #[indexed] action: ManagedBuffer,
+        error_message: ManagedBuffer,
+    );
+}
@@ -0,0 +1,228 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+/// Maximum number of active vesting schedules a beneficiary can hold for one token
+const MAX_SCHEDULES_PER_BENEFICIARY: usize = 20;
+
+/// How the tokens of a vesting schedule are released
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq)]
+pub enum VestingKind {
+    /// Tokens are released proportionally between the start and the end timestamp
+    Linear,
+    /// All tokens are released at once at the end timestamp
+    Cliff,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct VestingSchedule<M: ManagedTypeApi> {
+    pub token_identifier: TokenIdentifier<M>,
+    pub beneficiary: ManagedAddress<M>,
+    pub kind: VestingKind,
+    pub total_amount: BigUint<M>,
+    pub claimed_amount: BigUint<M>,
+    pub start_timestamp: u64,
+    pub end_timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct VestingStatus<M: ManagedTypeApi> {
+    pub schedule_id: u64,
+    pub token_identifier: TokenIdentifier<M>,
+    pub locked_amount: BigUint<M>,
+    pub vested_amount: BigUint<M>,
+    pub claimed_amount: BigUint<M>,
+}
+
+/// Module keeping track of the vesting schedules created from custodial balances.
+#[multiversx_sc::module]
+pub trait VestingModule {
+    /// Storage to hold each vesting schedule by id
+    #[storage_mapper("vesting_schedule")]
+    fn vesting_schedule(&self, schedule_id: u64) -> SingleValueMapper<VestingSchedule<Self::Api>>;
+
+    /// Storage to track the active vesting schedules of each beneficiary, by token
+    #[storage_mapper("beneficiary_schedules")]
+    fn beneficiary_schedules(
+        &self,
+        beneficiary: &ManagedAddress,
+        token_identifier: &TokenIdentifier,
+    ) -> UnorderedSetMapper<u64>;
+
+    /// Storage to track the tokens for which a beneficiary holds active vesting schedules
+    #[storage_mapper("beneficiary_vesting_tokens")]
+    fn beneficiary_vesting_tokens(
+        &self,
+        beneficiary: &ManagedAddress,
+    ) -> UnorderedSetMapper<TokenIdentifier>;
+
+    /// Storage to hold the id of the last created vesting schedule
+    #[storage_mapper("last_vesting_schedule_id")]
+    fn last_vesting_schedule_id(&self) -> SingleValueMapper<u64>;
+
+    /// View endpoint to query the locked, vested and claimed amounts of each schedule of a beneficiary
+    #[view(get_vesting_schedules)]
+    fn get_vesting_schedules(
+        &self,
+        beneficiary: ManagedAddress,
+    ) -> MultiValueEncoded<VestingStatus<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+
+        for token_identifier in self.beneficiary_vesting_tokens(&beneficiary).iter() {
+            for schedule_id in self
+                .beneficiary_schedules(&beneficiary, &token_identifier)
+                .iter()
+            {
+                let schedule = self.vesting_schedule(schedule_id).get();
+                let vested_amount = self.compute_vested_amount(&schedule);
+
+                result.push(VestingStatus {
+                    schedule_id,
+                    token_identifier: schedule.token_identifier,
+                    locked_amount: &schedule.total_amount - &vested_amount,
+                    vested_amount,
+                    claimed_amount: schedule.claimed_amount,
+                });
+            }
+        }
+
+        result
+    }
+
+    /// Stores a new vesting schedule and returns its id
+    fn add_vesting_schedule(
+        &self,
+        token_identifier: TokenIdentifier,
+        beneficiary: ManagedAddress,
+        kind: VestingKind,
+        total_amount: BigUint,
+        start_timestamp: u64,
+        end_timestamp: u64,
+    ) -> u64 {
+        require!(total_amount > 0, "Vesting amount must be greater than zero");
+        require!(
+            end_timestamp > start_timestamp,
+            "Vesting end must be after vesting start"
+        );
+
+        require!(
+            self.beneficiary_schedules(&beneficiary, &token_identifier).len()
+                < MAX_SCHEDULES_PER_BENEFICIARY,
+            "Beneficiary has too many active vesting schedules for this token"
+        );
+
+        let schedule_id = self.last_vesting_schedule_id().get() + 1;
+        self.last_vesting_schedule_id().set(schedule_id);
+
+        self.beneficiary_schedules(&beneficiary, &token_identifier)
+            .insert(schedule_id);
+        self.beneficiary_vesting_tokens(&beneficiary)
+            .insert(token_identifier.clone());
+        self.vesting_schedule(schedule_id).set(VestingSchedule {
+            token_identifier: token_identifier.clone(),
+            beneficiary: beneficiary.clone(),
+            kind,
+            total_amount: total_amount.clone(),
+            claimed_amount: BigUint::zero(),
+            start_timestamp,
+            end_timestamp,
+        });
+
+        self.vesting_schedule_created_event(
+            schedule_id,
+            token_identifier,
+            beneficiary,
+            total_amount,
+        );
+
+        schedule_id
+    }
+
+    /// Marks all vested but unclaimed tokens of a beneficiary as claimed and returns their amount.
+    /// Fully claimed schedules are removed from storage.
+    fn release_vested_tokens(
+        &self,
+        beneficiary: &ManagedAddress,
+        token_identifier: &TokenIdentifier,
+    ) -> BigUint {
+        let mut released = BigUint::zero();
+        let mut schedules = self.beneficiary_schedules(beneficiary, token_identifier);
+
+        // Collect the ids first, finished schedules are removed from the set while releasing
+        let mut schedule_ids = ManagedVec::<Self::Api, u64>::new();
+        for schedule_id in schedules.iter() {
+            schedule_ids.push(schedule_id);
+        }
+
+        for schedule_id in schedule_ids.iter() {
+            let mut schedule = self.vesting_schedule(schedule_id).get();
+
+            let claimable = self.compute_vested_amount(&schedule) - &schedule.claimed_amount;
+            if claimable == 0 {
+                continue;
+            }
+
+            schedule.claimed_amount += &claimable;
+            released += &claimable;
+
+            if schedule.claimed_amount == schedule.total_amount {
+                schedules.swap_remove(&schedule_id);
+                self.vesting_schedule(schedule_id).clear();
+            } else {
+                self.vesting_schedule(schedule_id).set(schedule);
+            }
+
+            self.vested_tokens_released_event(schedule_id, beneficiary.clone(), claimable);
+        }
+
+        if schedules.is_empty() {
+            self.beneficiary_vesting_tokens(beneficiary)
+                .swap_remove(token_identifier);
+        }
+
+        released
+    }
+
+    /// Computes the amount of a schedule vested at the current block timestamp
+    fn compute_vested_amount(&self, schedule: &VestingSchedule<Self::Api>) -> BigUint {
+        let current_timestamp = self.blockchain().get_block_timestamp();
+
+        if current_timestamp >= schedule.end_timestamp {
+            return schedule.total_amount.clone();
+        }
+
+        match schedule.kind {
+            VestingKind::Cliff => BigUint::zero(),
+            VestingKind::Linear => {
+                if current_timestamp <= schedule.start_timestamp {
+                    return BigUint::zero();
+                }
+
+                let elapsed = current_timestamp - schedule.start_timestamp;
+                let duration = schedule.end_timestamp - schedule.start_timestamp;
+                schedule.total_amount.clone() * elapsed / duration
+            }
+        }
+    }
+
+    /// Event emitted when a vesting schedule is created.
+    #[event("vesting_schedule_created")]
+    fn vesting_schedule_created_event(
+        &self,
+        #[indexed] schedule_id: u64,
+        #[indexed] token_identifier: TokenIdentifier,
+        #[indexed] beneficiary: ManagedAddress,
+        total_amount: BigUint,
+    );
+
+    /// Event emitted when vested tokens are released to a beneficiary.
+    #[event("vested_tokens_released")]
+    fn vested_tokens_released_event(
+        &self,
+        #[indexed] schedule_id: u64,
+        #[indexed] beneficiary: ManagedAddress,
+        amount: BigUint,
+    );
+}
@@ -8,3 +8,42 @@ fn world() -> ScenarioWorld {
 fn empty_go() {
     world().run("scenarios/issue_token_snow_sc.scen.json");
 }
+
+#[test]
+fn vesting_go() {
+    world().run("scenarios/vesting.scen.json");
+}
+
+#[test]
+fn airdrop_go() {
+    world().run("scenarios/airdrop.scen.json");
+}
+
+// The Rust VM simulates the ESDT system smart contract and accepts every issuance,
+// so the refund of a rejected issuance is only exercised on the Go VM
+#[test]
+fn issuance_refund_go() {
+    world().run("scenarios/issuance_refund.scen.json");
+}
+
+#[test]
+fn bonding_curve_go() {
+    world().run("scenarios/bonding_curve.scen.json");
+}
+
+#[test]
+fn collections_go() {
+    world().run("scenarios/collections.scen.json");
+}
+
+#[test]
+fn role_management_go() {
+    world().run("scenarios/role_management.scen.json");
+}
+
+// The Rust VM simulates the ESDT system smart contract, so the mock that records
+// and rejects token management requests is only deployed on the Go VM
+#[test]
+fn role_management_calls_go() {
+    world().run("scenarios/role_management_calls.scen.json");
+}
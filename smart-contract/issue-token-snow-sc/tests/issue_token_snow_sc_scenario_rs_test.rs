@@ -5,6 +5,7 @@ fn world() -> ScenarioWorld {
 
     // blockchain.set_current_dir_from_workspace("relative path to your workspace, if applicable");
     blockchain.register_contract("mxsc:output/issue-token-snow-sc.mxsc.json", issue_token_snow_sc::ContractBuilder);
+    blockchain.register_contract("mxsc:mock-esdt-system-sc/output/mock-esdt-system-sc.mxsc.json", mock_esdt_system_sc::ContractBuilder);
     blockchain
 }
 
@@ -12,3 +13,34 @@ fn world() -> ScenarioWorld {
 fn empty_rs() {
     world().run("scenarios/issue_token_snow_sc.scen.json");
 }
+
+#[test]
+fn vesting_rs() {
+    world().run("scenarios/vesting.scen.json");
+}
+
+#[test]
+fn airdrop_rs() {
+    world().run("scenarios/airdrop.scen.json");
+}
+
+#[test]
+fn bonding_curve_rs() {
+    world().run("scenarios/bonding_curve.scen.json");
+}
+
+#[test]
+fn collections_rs() {
+    world().run("scenarios/collections.scen.json");
+}
+
+#[test]
+fn role_management_rs() {
+    world().run("scenarios/role_management.scen.json");
+}
+
+// Issuing unnamed tokens relies on the ESDT system smart contract simulated by the Rust VM
+#[test]
+fn generated_names_rs() {
+    world().run("scenarios/generated_names.scen.json");
+}
@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                            5
+// Endpoints:                            34
 // Async Callback:                       1
-// Total number of exported functions:   8
+// Total number of exported functions:   37
 
 #![no_std]
 
@@ -20,11 +20,40 @@ multiversx_sc_wasm_adapter::endpoints! {
     (
         init => init
         upgrade => upgrade
+        get_collection => collection_by_ticker
+        get_collection_type => collection_type
         issue_token_snow => issue_token_snow
         issue_token_snow_and_transfer => issue_token_snow_and_transfer
+        issue_collection => issue_collection
         claim_tokens => claim_tokens
+        create_vesting_schedule => create_vesting_schedule
+        list_token_for_sale => list_token_for_sale
         burn_token => burn_token
         get_account_tokens => get_account_tokens
+        get_next_token_name => get_next_token_name
+        get_token_issuer => token_issuer
+        set_special_roles => set_special_roles
+        set_collection_roles => set_collection_roles
+        unset_special_roles => unset_special_roles
+        freeze_account => freeze_account
+        unfreeze_account => unfreeze_account
+        wipe_account => wipe_account
+        pause_token => pause_token
+        unpause_token => unpause_token
+        get_vesting_schedules => get_vesting_schedules
+        get_airdrop => airdrop_distribution
+        create_airdrop => create_airdrop
+        claim_airdrop => claim_airdrop
+        reclaim_airdrop => reclaim_airdrop
+        is_airdrop_claimed => is_airdrop_claimed
+        get_token_sale => token_sale
+        get_curve_balance => curve_balance
+        buy => buy
+        sell => sell
+        close_token_sale => close_token_sale
+        withdraw_sale_reserve => withdraw_sale_reserve
+        get_buy_quote => get_buy_quote
+        get_sell_quote => get_sell_quote
     )
 }
 
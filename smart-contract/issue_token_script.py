@@ -14,7 +14,9 @@ ABI_PATH = "./issue-token-snow-sc/output/issue-token-snow-sc.abi.json"
 SC_ADDRESS = "erd1qqqqqqqqqqqqqpgqmm40w8anjxdr9mrtcag0a4ydhg4a9ukfq7vqrfujc7"  # Replace with your smart contract address
 SC_OWNER_WALLET_PATH = "../3-dec/output"  # Directory containing wallet files
 TOKEN_NAME = "SantaClaus"  # Leave empty to generate a random name
+TOKEN_TICKER = "SNOW"  # 3-10 uppercase alphanumeric characters
 TOKEN_SUPPLY = 1_000_000_000
+TOKEN_DECIMALS = 8  # 0-18
 ISSUE_COST = 0.06 * 10**18  # 0.06 EGLD in smallest denomination
 TOKEN_GAS_LIMIT = 60_000_000
 
@@ -50,7 +52,9 @@ def issue_snow_tokens(endpoint: str, wallet_path: Path):
 
     # Prepare the payload
     name_hex = Utilities.encode_to_hex(TOKEN_NAME)
+    ticker_hex = Utilities.encode_to_hex(TOKEN_TICKER)
     supply_hex = Utilities.encode_to_hex(TOKEN_SUPPLY)
+    decimals_hex = Utilities.encode_to_hex(TOKEN_DECIMALS)
     properties_hex = [
         Utilities.encode_boolean(TOKEN_PROPERTIES["can_freeze"]),
         Utilities.encode_boolean(TOKEN_PROPERTIES["can_wipe"]),
@@ -62,7 +66,7 @@ def issue_snow_tokens(endpoint: str, wallet_path: Path):
         Utilities.encode_boolean(TOKEN_PROPERTIES["can_add_special_roles"]),
     ]
 
-    payload = f"{endpoint}@{name_hex}@{supply_hex}@" + "@".join(properties_hex)
+    payload = f"{endpoint}@{name_hex}@{ticker_hex}@{supply_hex}@{decimals_hex}@" + "@".join(properties_hex)
     logging.info(f"Payload: {payload}")
 
     # Create and sign the transaction
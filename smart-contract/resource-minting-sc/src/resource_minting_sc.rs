@@ -3,14 +3,27 @@
 #[allow(unused_imports)]
 use multiversx_sc::imports::*;
 
+use resources::{ResourceShare, SPLIT_TOTAL_PERCENTAGE};
+
+pub mod resources;
+
+/// Number of staked WINTER tokens the production rate of a resource refers to
+const STAKE_UNIT: u64 = 1_000;
+
 /// Smart contract for minting resources based on staked "WINTER" tokens.
 #[multiversx_sc::contract]
-pub trait ResourceMintingSc {
-    /// Initializes the smart contract
+pub trait ResourceMintingSc: resources::ResourcesModule {
+    /// Initializes the smart contract, optionally with resources given as
+    /// token identifier, rounds per cycle and units produced per 1000 WINTER
     #[init]
-    fn init(&self) {}
+    fn init(&self, resources: MultiValueEncoded<MultiValue3<TokenIdentifier, u64, u64>>) {
+        for resource in resources.into_iter() {
+            let (token, rounds_per_cycle, units_per_thousand) = resource.into_tuple();
+            self.add_resource_internal(token, rounds_per_cycle, units_per_thousand);
+        }
+    }
 
-    /// Storage to track staked tokens for each user
+    /// Legacy storage of the staked tokens for each user, kept until its entries are migrated
     #[storage_mapper("stakes")]
     fn stakes(
         &self,
@@ -19,17 +32,44 @@ pub trait ResourceMintingSc {
         BigUint<Self::Api>,
     >;
 
-    /// Storage to track last minting round for each user
-    #[storage_mapper("last_minting_round")]
-    fn last_minting_round(&self, user: &ManagedAddress) -> SingleValueMapper<u64>;
+    /// Storage to track the tokens staked by each user
+    #[storage_mapper("user_staked_tokens")]
+    fn user_staked_tokens(&self, user: &ManagedAddress) -> UnorderedSetMapper<TokenIdentifier>;
+
+    /// Storage to hold the staked balance of each user in each token
+    #[view(get_user_stake)]
+    #[storage_mapper("user_stake")]
+    fn user_stake(
+        &self,
+        user: &ManagedAddress,
+        token: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
 
-    /// Storage to track unclaimed resources for each user
+    /// Storage to hold the amount staked by all users in each token
+    #[view(get_total_stake)]
+    #[storage_mapper("total_stake")]
+    fn total_stake(&self, token: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    /// Storage to track unclaimed resources for each user and resource
     #[storage_mapper("unclaimed_resources")]
-    fn unclaimed_resources(&self, user: &ManagedAddress) -> SingleValueMapper<BigUint<Self::Api>>;
+    fn unclaimed_resources(
+        &self,
+        user: &ManagedAddress,
+        token: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint<Self::Api>>;
+
+    /// Storage to accumulate, for each user and resource, the stake multiplied by its percentage
+    /// of the split for every round since the last mint
+    #[storage_mapper("production_weight")]
+    fn production_weight(
+        &self,
+        user: &ManagedAddress,
+        token: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
 
-    /// Storage to track the token identifier for resource minting
-    #[storage_mapper("resource_token_identifier")]
-    fn resource_token_identifier(&self, user: &ManagedAddress) -> SingleValueMapper<TokenIdentifier<Self::Api>>;
+    /// Storage to track the last round up to which the production of each user was accumulated
+    #[storage_mapper("last_accrual_round")]
+    fn last_accrual_round(&self, user: &ManagedAddress) -> SingleValueMapper<u64>;
 
     /// Endpoint to stake WINTER tokens
     #[payable("*")]
@@ -43,6 +83,9 @@ pub trait ResourceMintingSc {
             "At least one token must be staked"
         );
 
+        // Production of the elapsed rounds is computed with the previous stake
+        self.settle_production(&caller);
+
         for payment in self.call_value().all_esdt_transfers().iter() {
             let token_bytes = payment.token_identifier.as_managed_buffer().to_boxed_bytes();
             let token_bytes_slice = token_bytes.as_ref();
@@ -56,95 +99,260 @@ pub trait ResourceMintingSc {
 
             require!(payment.amount > 0, "Token amount must be greater than zero");
 
-            let key = (caller.clone(), payment.token_identifier.clone());
-            let current_stake = self.stakes().get(&key).unwrap_or_default();
-            let new_stake = current_stake + payment.amount.clone();
+            self.increase_stake(&caller, &payment.token_identifier, &payment.amount);
+        }
+    }
+
+    /// Endpoint to unstake WINTER tokens, after accumulating the production of the elapsed rounds
+    #[endpoint(unstake_winter)]
+    fn unstake_winter(&self, token: TokenIdentifier, amount: BigUint) {
+        let caller = self.blockchain().get_caller();
+        require!(amount > 0, "Token amount must be greater than zero");
+
+        // Production of the elapsed rounds is computed with the previous stake
+        self.settle_production(&caller);
+
+        self.decrease_stake(&caller, &token, &amount);
+        self.send().direct_esdt(&caller, &token, 0, &amount);
+
+        // Emit event for unstaking
+        self.unstake_event(caller, (token, amount));
+    }
+
+    /// Endpoint for the owner to migrate up to `max_entries` legacy `stakes` entries.
+    /// Production is not settled until every entry is migrated. Returns the number of entries left.
+    #[only_owner]
+    #[endpoint(migrate_legacy_stakes)]
+    fn migrate_legacy_stakes(&self, max_entries: usize) -> usize {
+        for _ in 0..max_entries {
+            let ((user, token), amount) = match self.stakes().iter().next() {
+                Some(entry) => entry,
+                None => break,
+            };
 
-            self.stakes().insert(key, new_stake);
+            // Legacy users never settled production, so their stake counts from their last mint
+            self.stakes().remove(&(user.clone(), token.clone()));
+            self.increase_stake(&user, &token, &amount);
+
+            // Emit migration event
+            self.legacy_stake_migrated_event(user, (token, amount));
         }
 
-        // Set the last minting round to the current block round if not already set
-        self.last_minting_round(&caller)
-            .set_if_empty(self.blockchain().get_block_round());
+        self.stakes().len()
     }
 
-    /// Endpoint to mint resource tokens
-    #[endpoint(mint_resources)]
-    fn mint_resources(&self) {
+    /// View endpoint returning the staked amount of each token of a user
+    #[view(get_stakes)]
+    fn get_stakes(
+        &self,
+        user: ManagedAddress,
+    ) -> MultiValueEncoded<MultiValue2<TokenIdentifier, BigUint>> {
+        let mut result = MultiValueEncoded::new();
+
+        for token in self.user_staked_tokens(&user).iter() {
+            let amount = self.user_stake(&user, &token).get();
+            result.push((token, amount).into());
+        }
+
+        result
+    }
+
+    /// Endpoint to choose the resources produced by the caller's stake.
+    /// Percentages must add up to 100, a single resource takes the whole stake.
+    #[endpoint(set_resource_split)]
+    fn set_resource_split(&self, shares: MultiValueEncoded<MultiValue2<TokenIdentifier, u64>>) {
         let caller = self.blockchain().get_caller();
         let current_round = self.blockchain().get_block_round();
-        let last_round = self.last_minting_round(&caller).get();
-        let unclaimed = self.unclaimed_resources(&caller).get();
 
-        // Ensure no unclaimed resources exist
-        require!(
-            unclaimed == BigUint::zero(),
-            "Unclaimed resources must be claimed before minting"
-        );
-
-        let resource_rounds = if self.blockchain().get_sc_address().as_managed_buffer().to_boxed_bytes().as_ref().starts_with(b"WOOD") {
-            600
-        } else if self.blockchain().get_sc_address().as_managed_buffer().to_boxed_bytes().as_ref().starts_with(b"FOOD") {
-            1200
-        } else if self.blockchain().get_sc_address().as_managed_buffer().to_boxed_bytes().as_ref().starts_with(b"STONE") {
-            1800
-        } else if self.blockchain().get_sc_address().as_managed_buffer().to_boxed_bytes().as_ref().starts_with(b"GOLD") {
-            2400
-        } else {
-            require!(false, "Invalid resource contract identifier");
-            return;
-        };
+        let mut split: ManagedVec<ResourceShare<Self::Api>> = ManagedVec::new();
+        let mut total_percentage = 0u64;
+        for share in shares.into_iter() {
+            let (token_identifier, percentage) = share.into_tuple();
+            require!(self.resources().contains(&token_identifier), "Unknown resource");
+            require!(percentage > 0, "Resource percentage must be greater than zero");
+            require!(
+                percentage <= SPLIT_TOTAL_PERCENTAGE,
+                "Resource percentage must be at most 100"
+            );
+            require!(
+                !split.iter().any(|existing| existing.token_identifier == token_identifier),
+                "Duplicate resource"
+            );
 
-        // Ensure enough rounds have passed for minting
+            total_percentage += percentage;
+            split.push(ResourceShare {
+                token_identifier,
+                percentage,
+            });
+        }
         require!(
-            current_round >= last_round + resource_rounds,
-            "Not enough rounds have passed for minting"
+            total_percentage == SPLIT_TOTAL_PERCENTAGE,
+            "Resource percentages must add up to 100"
         );
 
-        // Calculate total mintable resources
-        let mut total_mintable = BigUint::zero();
-        for ((user_address, _token_identifier), staked_amount) in self.stakes().iter() {
-            if user_address == caller {
-                let mintable = staked_amount / 1000u64; // 1 unit per 1000 tokens
-                total_mintable += mintable.clone();
+        // Production of the elapsed rounds is computed with the previous split
+        self.settle_production(&caller);
 
-                // Emit event for resource minting
-                self.resource_mint_event(user_address, mintable.clone());
+        // Resources no longer produced stop their cycle, new ones start it now
+        for previous in self.resource_split(&caller).get().iter() {
+            if !split.iter().any(|share| share.token_identifier == previous.token_identifier) {
+                self.last_minting_round(&caller, &previous.token_identifier).clear();
+                self.production_weight(&caller, &previous.token_identifier).clear();
             }
         }
+        for share in split.iter() {
+            self.last_minting_round(&caller, &share.token_identifier).set_if_empty(current_round);
+        }
 
-        require!(total_mintable > 0, "No resources to mint");
+        self.resource_split(&caller).set(&split);
 
-        // Save the resource token identifier for minting
-        let resource_token_id = TokenIdentifier::from(self.blockchain().get_sc_address().as_managed_buffer().clone());
-        self.resource_token_identifier(&caller).set(resource_token_id.clone());
+        // Emit an event for the resource split update
+        self.resource_split_set_event(caller, split);
+    }
 
-        // Update unclaimed resources
-        self.unclaimed_resources(&caller).set(total_mintable.clone());
+    /// Endpoint to mint the resources of all cycles elapsed since the last mint, following the
+    /// caller's resource split
+    #[endpoint(mint_resources)]
+    fn mint_resources(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(!self.resource_split(&caller).is_empty(), "No resource selected");
 
-        // Update the last minting round
-        self.last_minting_round(&caller).set(current_round);
+        let (elapsed_resources, total_mintable) = self.settle_production(&caller);
+
+        // Ensure enough rounds have passed for minting
+        require!(elapsed_resources > 0, "Not enough rounds have passed for minting");
+        require!(total_mintable > 0, "No resources to mint");
     }
 
-    /// Endpoint to claim minted resources
+    /// Endpoint to claim all minted resources
     #[endpoint(claim_resources)]
     fn claim_resources(&self) {
         let caller = self.blockchain().get_caller();
-        let unclaimed = self.unclaimed_resources(&caller).get();
-        let resource_token_id = self.resource_token_identifier(&caller).get();
 
-        require!(unclaimed > BigUint::zero(), "No resources to claim");
+        let mut payments = ManagedVec::new();
+        for token in self.resources().iter() {
+            let unclaimed = self.unclaimed_resources(&caller, &token).take();
+            if unclaimed == 0 {
+                continue;
+            }
 
-        self.send().esdt_local_mint(
-            &resource_token_id,
-            0,
-            &unclaimed,
-        );
-        self.send().direct_esdt(&caller, &resource_token_id, 0, &unclaimed);
+            self.send().esdt_local_mint(&token, 0, &unclaimed);
+            payments.push(EsdtTokenPayment::new(token, 0, unclaimed));
+        }
+
+        require!(!payments.is_empty(), "No resources to claim");
+
+        self.send().direct_multi(&caller, &payments);
+    }
+
+    /// Accumulates the production of the rounds elapsed since the last accrual with the current
+    /// stake, then adds the production of all cycles finished since the last mint to the unclaimed
+    /// resources of a user, up to the storage limit of each resource.
+    /// The rounds of an unfinished cycle count towards the next mint.
+    /// Returns the number of resources with at least one elapsed cycle and the total amount added.
+    fn settle_production(&self, user: &ManagedAddress) -> (usize, BigUint) {
+        // Legacy stakes are not counted, so production waits for their migration
+        require!(self.stakes().is_empty(), "Legacy stakes must be migrated first");
+
+        let current_round = self.blockchain().get_block_round();
+        let last_accrual_round = self.last_accrual_round(user).get();
+        let total_stake = self.user_total_stake(user);
+        self.last_accrual_round(user).set(current_round);
+
+        let mut elapsed_resources = 0usize;
+        let mut total_mintable = BigUint::zero();
+        for share in self.resource_split(user).get().iter() {
+            let token = share.token_identifier;
+
+            // Resources removed from the registry do not produce until they are added back
+            if !self.resources().contains(&token) {
+                self.last_minting_round(user, &token).set(current_round);
+                self.production_weight(user, &token).clear();
+                continue;
+            }
+
+            let definition = self.resource_definition(&token).get();
+            let last_round = self.last_minting_round(user, &token).get();
+
+            // The stake has not changed since the last accrual, so it counts for every round since
+            let accrual_start = core::cmp::max(last_accrual_round, last_round);
+            let weight = self.production_weight(user, &token).get()
+                + total_stake.clone() * share.percentage * (current_round - accrual_start);
+
+            let elapsed_cycles = (current_round - last_round) / definition.rounds_per_cycle;
+            if elapsed_cycles == 0 {
+                self.production_weight(user, &token).set(weight);
+                continue;
+            }
+            elapsed_resources += 1;
+
+            // Rounds after the end of the last finished cycle count towards the next mint
+            let cycle_end = last_round + elapsed_cycles * definition.rounds_per_cycle;
+            let carried_weight =
+                total_stake.clone() * share.percentage * (current_round - cycle_end);
+            let produced = (weight - &carried_weight) * definition.units_per_thousand
+                / (STAKE_UNIT * SPLIT_TOTAL_PERCENTAGE * definition.rounds_per_cycle);
+            self.production_weight(user, &token).set(carried_weight);
+
+            // Production beyond the storage limit is lost
+            let unclaimed = self.unclaimed_resources(user, &token).get();
+            let limit = self.resource_storage_limit(&token).get();
+            let mintable = if limit > 0 && &unclaimed + &produced > limit {
+                if unclaimed < limit {
+                    limit - &unclaimed
+                } else {
+                    BigUint::zero()
+                }
+            } else {
+                produced
+            };
+
+            if mintable > 0 {
+                self.unclaimed_resources(user, &token).set(unclaimed + &mintable);
+                total_mintable += &mintable;
+
+                // Emit event for resource minting
+                self.resource_mint_event(user.clone(), token.clone(), mintable);
+            }
+
+            // Update the last minting round
+            self.last_minting_round(user, &token).set(cycle_end);
+        }
+
+        (elapsed_resources, total_mintable)
+    }
+
+    /// Increases the staked balance of a user in a token
+    fn increase_stake(&self, user: &ManagedAddress, token: &TokenIdentifier, amount: &BigUint) {
+        self.user_staked_tokens(user).insert(token.clone());
+        self.user_stake(user, token).update(|stake| *stake += amount);
+        self.total_stake(token).update(|total| *total += amount);
+    }
+
+    /// Decreases the staked balance of a user in a token
+    fn decrease_stake(&self, user: &ManagedAddress, token: &TokenIdentifier, amount: &BigUint) {
+        let current_stake = self.user_stake(user, token).get();
+        require!(&current_stake >= amount, "Not enough tokens staked");
+
+        let new_stake = current_stake - amount;
+        if new_stake == 0 {
+            self.user_stake(user, token).clear();
+            self.user_staked_tokens(user).swap_remove(token);
+        } else {
+            self.user_stake(user, token).set(new_stake);
+        }
 
-        // Clear unclaimed resources and token identifier
-        self.unclaimed_resources(&caller).clear();
-        self.resource_token_identifier(&caller).clear();
+        self.total_stake(token).update(|total| *total -= amount);
+    }
+
+    /// Returns the amount of WINTER tokens staked by a user
+    fn user_total_stake(&self, user: &ManagedAddress) -> BigUint {
+        let mut total_stake = BigUint::zero();
+        for token in self.user_staked_tokens(user).iter() {
+            total_stake += self.user_stake(user, &token).get();
+        }
+
+        total_stake
     }
 
     /// Emit an event for resource minting
@@ -152,9 +360,26 @@ pub trait ResourceMintingSc {
     fn resource_mint_event(
         &self,
         #[indexed] user: ManagedAddress,
+        #[indexed] token: TokenIdentifier,
         amount: BigUint,
     );
 
+    /// Emit an event for unstaking
+    #[event("unstake_event")]
+    fn unstake_event(
+        &self,
+        #[indexed] user: ManagedAddress,
+        unstake_data: (TokenIdentifier, BigUint),
+    );
+
+    /// Emit an event for migrating a legacy stake
+    #[event("legacy_stake_migrated_event")]
+    fn legacy_stake_migrated_event(
+        &self,
+        #[indexed] user: ManagedAddress,
+        stake_data: (TokenIdentifier, BigUint),
+    );
+
     /// Function to handle contract upgrades
     #[only_owner]
     #[upgrade]
@@ -0,0 +1,145 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+/// Total of the percentages of a resource split
+pub const SPLIT_TOTAL_PERCENTAGE: u64 = 100;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct ResourceDefinition {
+    pub rounds_per_cycle: u64,
+    pub units_per_thousand: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem, Clone)]
+pub struct ResourceShare<M: ManagedTypeApi> {
+    pub token_identifier: TokenIdentifier<M>,
+    pub percentage: u64,
+}
+
+/// Module holding the registry of resources the contract produces, and the resources each user's
+/// stake produces.
+#[multiversx_sc::module]
+pub trait ResourcesModule {
+    /// Storage to hold the resource tokens the contract produces
+    #[storage_mapper("resources")]
+    fn resources(&self) -> UnorderedSetMapper<TokenIdentifier>;
+
+    /// Storage to hold the cycle length and production rate of each resource
+    #[view(get_resource_definition)]
+    #[storage_mapper("resource_definition")]
+    fn resource_definition(&self, token: &TokenIdentifier) -> SingleValueMapper<ResourceDefinition>;
+
+    /// Storage to hold the highest amount of unclaimed units of each resource a user can hold,
+    /// zero when unlimited
+    #[view(get_resource_storage_limit)]
+    #[storage_mapper("resource_storage_limit")]
+    fn resource_storage_limit(&self, token: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    /// Storage to hold the resources produced by the stake of each user, with their percentage
+    #[view(get_resource_split)]
+    #[storage_mapper("resource_split")]
+    fn resource_split(
+        &self,
+        user: &ManagedAddress,
+    ) -> SingleValueMapper<ManagedVec<ResourceShare<Self::Api>>>;
+
+    /// Storage to track the last minting round of each user for each resource
+    #[storage_mapper("last_minting_round")]
+    fn last_minting_round(
+        &self,
+        user: &ManagedAddress,
+        token: &TokenIdentifier,
+    ) -> SingleValueMapper<u64>;
+
+    /// Endpoint for the owner to add a resource or update its cycle length and production rate
+    #[only_owner]
+    #[endpoint(add_resource)]
+    fn add_resource(&self, token: TokenIdentifier, rounds_per_cycle: u64, units_per_thousand: u64) {
+        self.add_resource_internal(token, rounds_per_cycle, units_per_thousand);
+    }
+
+    fn add_resource_internal(
+        &self,
+        token: TokenIdentifier,
+        rounds_per_cycle: u64,
+        units_per_thousand: u64,
+    ) {
+        require!(token.is_valid_esdt_identifier(), "Invalid resource token identifier");
+        require!(rounds_per_cycle > 0, "Resource rounds must be greater than zero");
+        require!(units_per_thousand > 0, "Resource rate must be greater than zero");
+
+        self.resources().insert(token.clone());
+        self.resource_definition(&token).set(ResourceDefinition {
+            rounds_per_cycle,
+            units_per_thousand,
+        });
+
+        // Emit an event for the resource update
+        self.resource_added_event(token, rounds_per_cycle, units_per_thousand);
+    }
+
+    /// Endpoint for the owner to limit the unclaimed units of a resource each user can hold.
+    /// Production beyond the limit is lost, a zero limit removes it.
+    #[only_owner]
+    #[endpoint(set_resource_storage_limit)]
+    fn set_resource_storage_limit(&self, token: TokenIdentifier, limit: BigUint) {
+        require!(self.resources().contains(&token), "Unknown resource");
+        self.resource_storage_limit(&token).set(&limit);
+
+        // Emit an event for the storage limit update
+        self.resource_storage_limit_set_event(token, limit);
+    }
+
+    /// Endpoint for the owner to stop producing a resource.
+    /// Unclaimed amounts of the resource can be claimed again once it is added back.
+    #[only_owner]
+    #[endpoint(remove_resource)]
+    fn remove_resource(&self, token: TokenIdentifier) {
+        require!(self.resources().swap_remove(&token), "Unknown resource");
+        self.resource_definition(&token).clear();
+        self.resource_storage_limit(&token).clear();
+
+        // Emit an event for the resource removal
+        self.resource_removed_event(token);
+    }
+
+    /// View endpoint returning the registered resources with their cycle length and production rate
+    #[view(get_resources)]
+    fn get_resources(&self) -> MultiValueEncoded<MultiValue3<TokenIdentifier, u64, u64>> {
+        let mut result = MultiValueEncoded::new();
+
+        for token in self.resources().iter() {
+            let definition = self.resource_definition(&token).get();
+            result.push((token, definition.rounds_per_cycle, definition.units_per_thousand).into());
+        }
+
+        result
+    }
+
+    /// Emit an event for adding or updating a resource
+    #[event("resource_added_event")]
+    fn resource_added_event(
+        &self,
+        #[indexed] token: TokenIdentifier,
+        #[indexed] rounds_per_cycle: u64,
+        #[indexed] units_per_thousand: u64,
+    );
+
+    /// Emit an event for removing a resource
+    #[event("resource_removed_event")]
+    fn resource_removed_event(&self, #[indexed] token: TokenIdentifier);
+
+    /// Emit an event for setting the storage limit of a resource
+    #[event("resource_storage_limit_set_event")]
+    fn resource_storage_limit_set_event(&self, #[indexed] token: TokenIdentifier, limit: BigUint);
+
+    /// Emit an event for setting the resources produced by a user's stake
+    #[event("resource_split_set_event")]
+    fn resource_split_set_event(
+        &self,
+        #[indexed] user: ManagedAddress,
+        split: ManagedVec<ResourceShare<Self::Api>>,
+    );
+}
@@ -8,3 +8,28 @@ fn world() -> ScenarioWorld {
 fn empty_go() {
     world().run("scenarios/resource_minting_sc.scen.json");
 }
+
+#[test]
+fn mint_resources_go() {
+    world().run("scenarios/mint_resources.scen.json");
+}
+
+#[test]
+fn accumulated_production_go() {
+    world().run("scenarios/accumulated_production.scen.json");
+}
+
+#[test]
+fn unstake_winter_go() {
+    world().run("scenarios/unstake_winter.scen.json");
+}
+
+#[test]
+fn stake_mid_cycle_go() {
+    world().run("scenarios/stake_mid_cycle.scen.json");
+}
+
+#[test]
+fn legacy_stakes_go() {
+    world().run("scenarios/legacy_stakes.scen.json");
+}
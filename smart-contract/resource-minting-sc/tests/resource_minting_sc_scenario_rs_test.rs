@@ -12,3 +12,28 @@ fn world() -> ScenarioWorld {
 fn empty_rs() {
     world().run("scenarios/resource_minting_sc.scen.json");
 }
+
+#[test]
+fn mint_resources_rs() {
+    world().run("scenarios/mint_resources.scen.json");
+}
+
+#[test]
+fn accumulated_production_rs() {
+    world().run("scenarios/accumulated_production.scen.json");
+}
+
+#[test]
+fn unstake_winter_rs() {
+    world().run("scenarios/unstake_winter.scen.json");
+}
+
+#[test]
+fn stake_mid_cycle_rs() {
+    world().run("scenarios/stake_mid_cycle.scen.json");
+}
+
+#[test]
+fn legacy_stakes_rs() {
+    world().run("scenarios/legacy_stakes.scen.json");
+}
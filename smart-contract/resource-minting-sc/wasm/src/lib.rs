@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                            3
+// Endpoints:                            16
 // Async Callback (empty):               1
-// Total number of exported functions:   6
+// Total number of exported functions:   19
 
 #![no_std]
 
@@ -20,9 +20,22 @@ multiversx_sc_wasm_adapter::endpoints! {
     (
         init => init
         upgrade => upgrade
+        get_user_stake => user_stake
+        get_total_stake => total_stake
         stake_winter => stake_winter
+        unstake_winter => unstake_winter
+        migrate_legacy_stakes => migrate_legacy_stakes
+        get_stakes => get_stakes
+        set_resource_split => set_resource_split
         mint_resources => mint_resources
         claim_resources => claim_resources
+        get_resource_definition => resource_definition
+        get_resource_storage_limit => resource_storage_limit
+        get_resource_split => resource_split
+        add_resource => add_resource
+        set_resource_storage_limit => set_resource_storage_limit
+        remove_resource => remove_resource
+        get_resources => get_resources
     )
 }
 
@@ -0,0 +1,130 @@
+#![no_std]
+
+use multiversx_sc::imports::*;
+
+/// Denominator of the exchange rates, expressed in basis points
+const RATE_DENOMINATOR: u64 = 10_000;
+
+/// State of an xExchange pair accepting swaps
+const STATE_ACTIVE: u8 = 1;
+
+/// Minimal xExchange pair used by the staking scenarios.
+/// Swaps the first token into the second one at the owner-set pool rate, and reports the owner-set
+/// safe rate as its safe price, so scenarios can move the pool price away from the safe price.
+/// Its views take the same arguments as the xExchange pair; its reserves are its token balances.
+#[multiversx_sc::contract]
+pub trait MockPair {
+    /// Initializes the pair with its tokens and rates, in basis points of the second token
+    /// received per unit of the first token
+    #[init]
+    fn init(
+        &self,
+        first_token: TokenIdentifier,
+        second_token: TokenIdentifier,
+        pool_rate: u64,
+        safe_rate: u64,
+    ) {
+        self.first_token().set(first_token);
+        self.second_token().set(second_token);
+        self.state().set(STATE_ACTIVE);
+        self.set_rates(pool_rate, safe_rate);
+    }
+
+    /// Storage to hold the token sold to the pair
+    #[view(getFirstTokenId)]
+    #[storage_mapper("first_token")]
+    fn first_token(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    /// Storage to hold the token bought from the pair
+    #[view(getSecondTokenId)]
+    #[storage_mapper("second_token")]
+    fn second_token(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    /// Storage to hold the rate swaps are executed at, in basis points
+    #[view(getPoolRate)]
+    #[storage_mapper("pool_rate")]
+    fn pool_rate(&self) -> SingleValueMapper<u64>;
+
+    /// Storage to hold the rate reported as safe price, in basis points
+    #[view(getSafeRate)]
+    #[storage_mapper("safe_rate")]
+    fn safe_rate(&self) -> SingleValueMapper<u64>;
+
+    /// Storage to hold the state of the pair, 1 while swaps are accepted
+    #[view(getState)]
+    #[storage_mapper("state")]
+    fn state(&self) -> SingleValueMapper<u8>;
+
+    /// Endpoint to change the state of the pair
+    #[endpoint(setPairState)]
+    fn set_pair_state(&self, state: u8) {
+        self.state().set(state);
+    }
+
+    /// View endpoint returning the balances of both tokens as reserves, and no LP supply
+    #[view(getReservesAndTotalSupply)]
+    fn get_reserves_and_total_supply(&self) -> MultiValue3<BigUint, BigUint, BigUint> {
+        let first_token = EgldOrEsdtTokenIdentifier::esdt(self.first_token().get());
+        let second_token = EgldOrEsdtTokenIdentifier::esdt(self.second_token().get());
+
+        (
+            self.blockchain().get_sc_balance(&first_token, 0),
+            self.blockchain().get_sc_balance(&second_token, 0),
+            BigUint::zero(),
+        )
+            .into()
+    }
+
+    /// Endpoint to change the pool and safe rates
+    #[endpoint(setRates)]
+    fn set_rates(&self, pool_rate: u64, safe_rate: u64) {
+        self.pool_rate().set(pool_rate);
+        self.safe_rate().set(safe_rate);
+    }
+
+    /// View endpoint returning the amount of the second token a swap would return
+    #[view(getAmountOut)]
+    fn get_amount_out(&self, token_in: TokenIdentifier, amount_in: BigUint) -> BigUint {
+        require!(token_in == self.first_token().get(), "Unknown token");
+
+        amount_in * self.pool_rate().get() / RATE_DENOMINATOR
+    }
+
+    /// View endpoint returning the value of a payment of the first token at the safe rate
+    #[view(getSafePriceByDefaultOffset)]
+    fn get_safe_price_by_default_offset(
+        &self,
+        pair_address: ManagedAddress,
+        input: EsdtTokenPayment<Self::Api>,
+    ) -> EsdtTokenPayment<Self::Api> {
+        require!(pair_address == self.blockchain().get_sc_address(), "Unknown pair");
+        require!(input.token_identifier == self.first_token().get(), "Unknown token");
+
+        let amount = input.amount * self.safe_rate().get() / RATE_DENOMINATOR;
+        EsdtTokenPayment::new(self.second_token().get(), 0, amount)
+    }
+
+    /// Endpoint swapping the first token sent as payment into the second token at the pool rate
+    #[payable("*")]
+    #[endpoint(swapTokensFixedInput)]
+    fn swap_tokens_fixed_input(
+        &self,
+        token_out: TokenIdentifier,
+        amount_out_min: BigUint,
+    ) -> EsdtTokenPayment<Self::Api> {
+        let payment = self.call_value().single_esdt();
+        require!(self.state().get() == STATE_ACTIVE, "Pair is not active");
+        require!(token_out == self.second_token().get(), "Unknown token");
+
+        let amount_out = self.get_amount_out(payment.token_identifier, payment.amount);
+        require!(amount_out >= amount_out_min, "Slippage exceeded");
+
+        let caller = self.blockchain().get_caller();
+        self.send().direct_esdt(&caller, &token_out, 0, &amount_out);
+
+        EsdtTokenPayment::new(token_out, 0, amount_out)
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+}
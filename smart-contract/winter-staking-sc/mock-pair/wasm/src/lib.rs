@@ -0,0 +1,37 @@
+// Code generated by the multiversx-sc build system. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+// Init:                                 1
+// Upgrade:                              1
+// Endpoints:                            11
+// Async Callback (empty):               1
+// Total number of exported functions:   14
+
+#![no_std]
+
+multiversx_sc_wasm_adapter::allocator!();
+multiversx_sc_wasm_adapter::panic_handler!();
+
+multiversx_sc_wasm_adapter::endpoints! {
+    mock_pair
+    (
+        init => init
+        upgrade => upgrade
+        getFirstTokenId => first_token
+        getSecondTokenId => second_token
+        getPoolRate => pool_rate
+        getSafeRate => safe_rate
+        getState => state
+        setPairState => set_pair_state
+        getReservesAndTotalSupply => get_reserves_and_total_supply
+        setRates => set_rates
+        getAmountOut => get_amount_out
+        getSafePriceByDefaultOffset => get_safe_price_by_default_offset
+        swapTokensFixedInput => swap_tokens_fixed_input
+    )
+}
+
+multiversx_sc_wasm_adapter::async_callback_empty! {}
@@ -0,0 +1,183 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+use crate::staking_tokens::MULTIPLIER_DENOMINATOR;
+
+/// Maximum number of recipients sharing the rewards of a user
+const MAX_REWARD_RECIPIENTS: usize = 5;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem, Clone)]
+pub struct RewardSplit<M: ManagedTypeApi> {
+    pub recipient: ManagedAddress<M>,
+    pub share: u64,
+}
+
+/// Module letting users redirect their claimed rewards to a beneficiary, or split them between
+/// several recipients in basis points. Both can expire at a given epoch, after which the rewards
+/// go back to the user.
+#[multiversx_sc::module]
+pub trait BeneficiaryModule {
+    /// Storage to track the beneficiary for reward transfers
+    #[view(get_beneficiary)]
+    #[storage_mapper("beneficiary")]
+    fn beneficiary(&self, user: &ManagedAddress) -> SingleValueMapper<ManagedAddress<Self::Api>>;
+
+    /// Storage to hold the recipients sharing the rewards of each user
+    #[view(get_reward_splits)]
+    #[storage_mapper("reward_splits")]
+    fn reward_splits(
+        &self,
+        user: &ManagedAddress,
+    ) -> SingleValueMapper<ManagedVec<RewardSplit<Self::Api>>>;
+
+    /// Storage to hold the epoch from which the beneficiary or splits of each user no longer apply,
+    /// zero when they never expire
+    #[view(get_beneficiary_expiry)]
+    #[storage_mapper("beneficiary_expiry")]
+    fn beneficiary_expiry(&self, user: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    /// Endpoint to set or update the beneficiary, optionally until an expiry epoch.
+    /// Replaces any reward splits.
+    #[endpoint(set_beneficiary)]
+    fn set_beneficiary(&self, new_beneficiary: ManagedAddress, expiry_epoch: OptionalValue<u64>) {
+        let caller = self.blockchain().get_caller();
+
+        // Ensure the beneficiary address is valid and different from the caller
+        require!(
+            new_beneficiary != caller,
+            "Beneficiary cannot be the same as the caller"
+        );
+
+        let expiry_epoch = expiry_epoch.into_option().unwrap_or_default();
+        self.require_valid_expiry(expiry_epoch);
+
+        self.reward_splits(&caller).clear();
+        self.beneficiary(&caller).set(new_beneficiary.clone());
+        self.beneficiary_expiry(&caller).set(expiry_epoch);
+
+        // Emit an event for beneficiary update
+        self.beneficiary_set_event(caller, new_beneficiary, expiry_epoch);
+    }
+
+    /// Endpoint to split the rewards between several recipients, optionally until an expiry epoch.
+    /// Shares are in basis points and must add up to 10000. Replaces the beneficiary.
+    #[endpoint(set_reward_splits)]
+    fn set_reward_splits(
+        &self,
+        expiry_epoch: u64,
+        splits: MultiValueEncoded<MultiValue2<ManagedAddress, u64>>,
+    ) {
+        let caller = self.blockchain().get_caller();
+        self.require_valid_expiry(expiry_epoch);
+
+        let mut reward_splits = ManagedVec::new();
+        let mut total_share = 0u64;
+        for split in splits.into_iter() {
+            let (recipient, share) = split.into_tuple();
+            require!(share > 0, "Reward share must be greater than zero");
+            require!(
+                share <= MULTIPLIER_DENOMINATOR,
+                "Reward share must be at most 10000"
+            );
+            require!(
+                !reward_splits.iter().any(|existing| existing.recipient == recipient),
+                "Duplicate reward recipient"
+            );
+
+            total_share += share;
+            reward_splits.push(RewardSplit { recipient, share });
+        }
+        require!(!reward_splits.is_empty(), "No reward recipients");
+        require!(
+            reward_splits.len() <= MAX_REWARD_RECIPIENTS,
+            "Too many reward recipients"
+        );
+        require!(
+            total_share == MULTIPLIER_DENOMINATOR,
+            "Reward shares must add up to 10000"
+        );
+
+        self.beneficiary(&caller).clear();
+        self.reward_splits(&caller).set(&reward_splits);
+        self.beneficiary_expiry(&caller).set(expiry_epoch);
+
+        // Emit an event for the reward splits update
+        self.reward_splits_set_event(caller, expiry_epoch, reward_splits);
+    }
+
+    /// Endpoint to remove the beneficiary and reward splits, rewards are then sent to the caller
+    #[endpoint(clear_beneficiary)]
+    fn clear_beneficiary(&self) {
+        let caller = self.blockchain().get_caller();
+        self.beneficiary(&caller).clear();
+        self.reward_splits(&caller).clear();
+        self.beneficiary_expiry(&caller).clear();
+
+        // Emit an event for clearing the beneficiary
+        self.beneficiary_cleared_event(caller);
+    }
+
+    /// Ensures an expiry epoch is either zero or in the future
+    fn require_valid_expiry(&self, expiry_epoch: u64) {
+        require!(
+            expiry_epoch == 0 || expiry_epoch > self.blockchain().get_block_epoch(),
+            "Expiry epoch must be in the future"
+        );
+    }
+
+    /// Sends claimed rewards to the beneficiary or split recipients of a user, or to the user when
+    /// none is set or they expired. Rounding leftovers go to the last recipient.
+    fn send_rewards(&self, user: &ManagedAddress, token: &TokenIdentifier, amount: &BigUint) {
+        let expiry_epoch = self.beneficiary_expiry(user).get();
+        let expired = expiry_epoch != 0 && self.blockchain().get_block_epoch() >= expiry_epoch;
+
+        if !expired && !self.reward_splits(user).is_empty() {
+            let reward_splits = self.reward_splits(user).get();
+            let last_index = reward_splits.len() - 1;
+            let mut remaining = amount.clone();
+            for (index, split) in reward_splits.iter().enumerate() {
+                let split_amount = if index == last_index {
+                    remaining.clone()
+                } else {
+                    amount.clone() * split.share / MULTIPLIER_DENOMINATOR
+                };
+                remaining -= &split_amount;
+
+                if split_amount > 0 {
+                    self.send().direct_esdt(&split.recipient, token, 0, &split_amount);
+                }
+            }
+            return;
+        }
+
+        let reward_recipient = if expired || self.beneficiary(user).is_empty() {
+            user.clone()
+        } else {
+            self.beneficiary(user).get()
+        };
+        self.send().direct_esdt(&reward_recipient, token, 0, amount);
+    }
+
+    /// Emit an event for setting a beneficiary
+    #[event("beneficiary_set_event")]
+    fn beneficiary_set_event(
+        &self,
+        #[indexed] user: ManagedAddress,
+        #[indexed] beneficiary: ManagedAddress,
+        #[indexed] expiry_epoch: u64,
+    );
+
+    /// Emit an event for setting the reward splits
+    #[event("reward_splits_set_event")]
+    fn reward_splits_set_event(
+        &self,
+        #[indexed] user: ManagedAddress,
+        #[indexed] expiry_epoch: u64,
+        splits: ManagedVec<RewardSplit<Self::Api>>,
+    );
+
+    /// Emit an event for clearing the beneficiary and reward splits
+    #[event("beneficiary_cleared_event")]
+    fn beneficiary_cleared_event(&self, #[indexed] user: ManagedAddress);
+}
@@ -0,0 +1,250 @@
+use multiversx_sc::imports::*;
+
+use crate::staking_tokens;
+use crate::staking_tokens::MULTIPLIER_DENOMINATOR;
+
+/// Maximum slippage accepted when swapping rewards, in basis points (10%)
+const MAX_COMPOUND_SLIPPAGE: u64 = 1_000;
+
+/// State reported by an xExchange pair while it accepts swaps
+const PAIR_STATE_ACTIVE: u8 = 1;
+
+/// Module letting users reinvest their rewards into their stake instead of receiving them.
+#[multiversx_sc::module]
+pub trait CompoundingModule: staking_tokens::StakingTokensModule {
+    /// Storage to hold whether the rewards of each user are restaked
+    #[view(is_auto_compound)]
+    #[storage_mapper("auto_compound")]
+    fn auto_compound(&self, user: &ManagedAddress) -> SingleValueMapper<bool>;
+
+    /// Storage to hold the pair contract used to swap rewards into a staking token
+    #[view(get_compound_swap_pair)]
+    #[storage_mapper("compound_swap_pair")]
+    fn compound_swap_pair(&self) -> SingleValueMapper<ManagedAddress>;
+
+    /// Storage to hold the staking token the rewards are swapped into
+    #[view(get_compound_token)]
+    #[storage_mapper("compound_token")]
+    fn compound_token(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    /// Storage to hold the highest slippage from the pair's safe price accepted by reward swaps,
+    /// in basis points
+    #[view(get_compound_max_slippage)]
+    #[storage_mapper("compound_max_slippage")]
+    fn compound_max_slippage(&self) -> SingleValueMapper<u64>;
+
+    /// Endpoint to enable or disable restaking the rewards of the caller
+    #[endpoint(set_auto_compound)]
+    fn set_auto_compound(&self, enabled: bool) {
+        let caller = self.blockchain().get_caller();
+        self.auto_compound(&caller).set(enabled);
+
+        // Emit an event for the compounding setting update
+        self.auto_compound_set_event(caller, enabled);
+    }
+
+    /// Endpoint for the owner to set the pair contract swapping rewards into a staking token, and
+    /// the highest slippage from the pair's safe price accepted by the swap, in basis points.
+    /// The pair must live in the same shard as this contract, since the swap is a synchronous call.
+    #[only_owner]
+    #[endpoint(set_compound_swap)]
+    fn set_compound_swap(
+        &self,
+        pair_address: ManagedAddress,
+        token: TokenIdentifier,
+        max_slippage: u64,
+    ) {
+        require!(
+            self.blockchain().is_smart_contract(&pair_address),
+            "Swap pair must be a smart contract"
+        );
+        self.require_staking_token(&token);
+        let (first_token, second_token) = self.pair_tokens(&pair_address);
+        require!(
+            first_token == token || second_token == token,
+            "Swap pair does not trade the compound token"
+        );
+        require!(
+            max_slippage <= MAX_COMPOUND_SLIPPAGE,
+            "Compound slippage must be at most 1000 basis points"
+        );
+
+        self.compound_swap_pair().set(&pair_address);
+        self.compound_token().set(&token);
+        self.compound_max_slippage().set(max_slippage);
+
+        // Emit an event for the swap path update
+        self.compound_swap_set_event(pair_address, token, max_slippage);
+    }
+
+    /// Endpoint for the owner to remove the swap path, rewards are then only compounded
+    /// when the reward token itself can be staked
+    #[only_owner]
+    #[endpoint(clear_compound_swap)]
+    fn clear_compound_swap(&self) {
+        self.compound_swap_pair().clear();
+        self.compound_token().clear();
+        self.compound_max_slippage().clear();
+    }
+
+    /// Turns minted rewards into a stakeable payment, either directly or through the swap pair.
+    /// Returns `None` when the rewards cannot be compounded and must be transferred instead, which
+    /// includes an inactive or empty pair and a pool price further from the safe price than the
+    /// accepted slippage.
+    fn compound_payment(
+        &self,
+        reward_token: &TokenIdentifier,
+        amount: &BigUint,
+    ) -> Option<EsdtTokenPayment<Self::Api>> {
+        if self.staking_tokens().contains(reward_token) {
+            return Some(EsdtTokenPayment::new(reward_token.clone(), 0, amount.clone()));
+        }
+
+        if self.compound_swap_pair().is_empty() {
+            return None;
+        }
+
+        // The target token may have been removed from the allow-list since the swap was configured
+        let token_out = self.compound_token().get();
+        if !self.staking_tokens().contains(&token_out) {
+            return None;
+        }
+
+        // A failing synchronous call reverts the whole claim, so the pair is checked first
+        let pair_address = self.compound_swap_pair().get();
+        if !self.can_swap(&pair_address, reward_token, &token_out) {
+            return None;
+        }
+
+        // The safe price averages the pool price over past blocks, so it cannot be moved by
+        // transactions around the claim
+        let safe_output = self
+            .tx()
+            .to(&pair_address)
+            .raw_call("getSafePriceByDefaultOffset")
+            .argument(&pair_address)
+            .argument(&EsdtTokenPayment::new(reward_token.clone(), 0, amount.clone()))
+            .original_result::<EsdtTokenPayment<Self::Api>>()
+            .returns(ReturnsResult)
+            .sync_call();
+        if safe_output.token_identifier != token_out {
+            return None;
+        }
+
+        let max_slippage = self.compound_max_slippage().get();
+        let min_output =
+            safe_output.amount * (MULTIPLIER_DENOMINATOR - max_slippage) / MULTIPLIER_DENOMINATOR;
+
+        let pool_output = self
+            .tx()
+            .to(&pair_address)
+            .raw_call("getAmountOut")
+            .argument(reward_token)
+            .argument(amount)
+            .original_result::<BigUint>()
+            .returns(ReturnsResult)
+            .sync_call();
+        if min_output == 0 || pool_output < min_output {
+            return None;
+        }
+
+        let back_transfers = self
+            .tx()
+            .to(&pair_address)
+            .raw_call("swapTokensFixedInput")
+            .argument(&token_out)
+            .argument(&min_output)
+            .single_esdt(reward_token, 0, amount)
+            .returns(ReturnsBackTransfers)
+            .sync_call();
+
+        let mut received = BigUint::zero();
+        for payment in back_transfers.esdt_payments.iter() {
+            if payment.token_identifier == token_out {
+                received += &payment.amount;
+            }
+        }
+        require!(received > 0, "Swap returned no tokens to compound");
+
+        Some(EsdtTokenPayment::new(token_out, 0, received))
+    }
+
+    /// Checks that the pair is active, trades the two tokens and holds reserves of both
+    fn can_swap(
+        &self,
+        pair_address: &ManagedAddress,
+        token_in: &TokenIdentifier,
+        token_out: &TokenIdentifier,
+    ) -> bool {
+        let state = self
+            .tx()
+            .to(pair_address)
+            .raw_call("getState")
+            .original_result::<u8>()
+            .returns(ReturnsResult)
+            .sync_call();
+        if state != PAIR_STATE_ACTIVE {
+            return false;
+        }
+
+        let (first_token, second_token) = self.pair_tokens(pair_address);
+        let trades_tokens = (&first_token == token_in && &second_token == token_out)
+            || (&first_token == token_out && &second_token == token_in);
+        if !trades_tokens {
+            return false;
+        }
+
+        let (first_reserve, second_reserve, _) = self
+            .tx()
+            .to(pair_address)
+            .raw_call("getReservesAndTotalSupply")
+            .original_result::<MultiValue3<BigUint, BigUint, BigUint>>()
+            .returns(ReturnsResult)
+            .sync_call()
+            .into_tuple();
+
+        first_reserve > 0 && second_reserve > 0
+    }
+
+    /// Queries the two tokens traded by a pair
+    fn pair_tokens(&self, pair_address: &ManagedAddress) -> (TokenIdentifier, TokenIdentifier) {
+        let first_token = self
+            .tx()
+            .to(pair_address)
+            .raw_call("getFirstTokenId")
+            .original_result::<TokenIdentifier>()
+            .returns(ReturnsResult)
+            .sync_call();
+        let second_token = self
+            .tx()
+            .to(pair_address)
+            .raw_call("getSecondTokenId")
+            .original_result::<TokenIdentifier>()
+            .returns(ReturnsResult)
+            .sync_call();
+
+        (first_token, second_token)
+    }
+
+    /// Emit an event for enabling or disabling compounding
+    #[event("auto_compound_set_event")]
+    fn auto_compound_set_event(&self, #[indexed] user: ManagedAddress, #[indexed] enabled: bool);
+
+    /// Emit an event for setting the compounding swap path
+    #[event("compound_swap_set_event")]
+    fn compound_swap_set_event(
+        &self,
+        #[indexed] pair_address: ManagedAddress,
+        #[indexed] token: TokenIdentifier,
+        #[indexed] max_slippage: u64,
+    );
+
+    /// Emit an event for compounding rewards into the stake
+    #[event("compound_event")]
+    fn compound_event(
+        &self,
+        #[indexed] user: ManagedAddress,
+        #[indexed] reward_amount: BigUint,
+        stake_data: (TokenIdentifier, BigUint),
+    );
+}
@@ -0,0 +1,172 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+/// Maximum number of emission periods walked by a single reward update, keeps the gas cost bounded.
+/// Longer idle intervals are caught up by the following updates.
+const MAX_PERIODS_PER_UPDATE: u64 = 52;
+
+/// Number of halvings after which the emission of a period is always zero
+const MAX_HALVINGS: u64 = 128;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct EmissionSchedule<M: ManagedTypeApi> {
+    pub start_timestamp: u64,
+    pub period_duration: u64,
+    pub initial_emission: BigUint<M>,
+    pub halving_periods: u64,
+    pub emission_cap: BigUint<M>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct EmissionInfo<M: ManagedTypeApi> {
+    pub current_period: u64,
+    pub period_emission: BigUint<M>,
+    pub period_duration: u64,
+    pub next_period_timestamp: u64,
+    pub total_emitted: BigUint<M>,
+    pub emission_cap: BigUint<M>,
+}
+
+/// Module defining how many reward tokens are emitted over time.
+/// Time is divided in emission periods of fixed duration. The emission of a period comes from the
+/// step table when one is set, and otherwise from the initial emission halved every `halving_periods`.
+/// The total emission never exceeds the cap.
+#[multiversx_sc::module]
+pub trait EmissionModule {
+    /// Storage to hold the emission schedule
+    #[storage_mapper("emission_schedule")]
+    fn emission_schedule(&self) -> SingleValueMapper<EmissionSchedule<Self::Api>>;
+
+    /// Storage to hold the emission of each period, the last step applies to all following periods
+    #[storage_mapper("emission_steps")]
+    fn emission_steps(&self) -> VecMapper<BigUint>;
+
+    /// Storage to hold the amount of reward tokens emitted to stakers so far
+    #[storage_mapper("total_emitted")]
+    fn total_emitted(&self) -> SingleValueMapper<BigUint>;
+
+    /// View endpoint returning the current emission period, its emission and the progress towards the cap
+    #[view(getEmissionInfo)]
+    fn get_emission_info(&self) -> EmissionInfo<Self::Api> {
+        require!(!self.emission_schedule().is_empty(), "Emission schedule is not set");
+
+        let schedule = self.emission_schedule().get();
+        let current_period = self.current_period(&schedule);
+
+        EmissionInfo {
+            current_period,
+            period_emission: self.period_emission(&schedule, current_period),
+            period_duration: schedule.period_duration,
+            next_period_timestamp: schedule.start_timestamp
+                + (current_period + 1) * schedule.period_duration,
+            total_emitted: self.total_emitted().get(),
+            emission_cap: schedule.emission_cap,
+        }
+    }
+
+    /// Stores a new emission schedule starting at the current timestamp
+    fn store_emission_schedule(
+        &self,
+        period_duration: u64,
+        initial_emission: BigUint,
+        halving_periods: u64,
+        emission_cap: BigUint,
+    ) {
+        require!(period_duration > 0, "Emission period must be greater than zero");
+        require!(
+            emission_cap >= self.total_emitted().get(),
+            "Emission cap is below the amount already emitted"
+        );
+
+        self.emission_schedule().set(EmissionSchedule {
+            start_timestamp: self.blockchain().get_block_timestamp(),
+            period_duration,
+            initial_emission,
+            halving_periods,
+            emission_cap,
+        });
+    }
+
+    /// Returns the emission period of the current timestamp
+    fn current_period(&self, schedule: &EmissionSchedule<Self::Api>) -> u64 {
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        if current_timestamp > schedule.start_timestamp {
+            (current_timestamp - schedule.start_timestamp) / schedule.period_duration
+        } else {
+            0
+        }
+    }
+
+    /// Returns the emission of a period, from the step table or from the halvings
+    fn period_emission(&self, schedule: &EmissionSchedule<Self::Api>, period: u64) -> BigUint {
+        let steps_count = self.emission_steps().len();
+        if steps_count > 0 {
+            let step_index = core::cmp::min(period as usize + 1, steps_count);
+            return self.emission_steps().get(step_index);
+        }
+
+        if schedule.halving_periods == 0 {
+            return schedule.initial_emission.clone();
+        }
+
+        let halvings = period / schedule.halving_periods;
+        if halvings >= MAX_HALVINGS {
+            return BigUint::zero();
+        }
+
+        schedule.initial_emission.clone() >> halvings as usize
+    }
+
+    /// Returns the emission between two timestamps, before applying the cap, and the timestamp it
+    /// was computed up to, which is earlier than `to` when too many periods are crossed at once
+    fn compute_emission(&self, from: u64, to: u64) -> (BigUint, u64) {
+        if self.emission_schedule().is_empty() {
+            return (BigUint::zero(), to);
+        }
+
+        let schedule = self.emission_schedule().get();
+        let mut cursor = core::cmp::max(from, schedule.start_timestamp);
+        let mut emission = BigUint::zero();
+
+        for _ in 0..MAX_PERIODS_PER_UPDATE {
+            if cursor >= to {
+                return (emission, to);
+            }
+
+            let period = (cursor - schedule.start_timestamp) / schedule.period_duration;
+            let period_end = schedule.start_timestamp + (period + 1) * schedule.period_duration;
+            let segment_end = core::cmp::min(period_end, to);
+
+            emission += self.period_emission(&schedule, period) * (segment_end - cursor)
+                / schedule.period_duration;
+            cursor = segment_end;
+        }
+
+        (emission, cursor)
+    }
+
+    /// Limits an emission to what is left under the cap
+    fn capped_emission(&self, emission: BigUint) -> BigUint {
+        let emission_cap = self.emission_schedule().get().emission_cap;
+        let total_emitted = self.total_emitted().get();
+        if total_emitted >= emission_cap {
+            return BigUint::zero();
+        }
+
+        let remaining = emission_cap - total_emitted;
+        if emission > remaining {
+            remaining
+        } else {
+            emission
+        }
+    }
+
+    /// Limits an emission to what is left under the cap and records it as emitted
+    fn record_emission(&self, emission: BigUint) -> BigUint {
+        let emitted = self.capped_emission(emission);
+        self.total_emitted().update(|total| *total += &emitted);
+        emitted
+    }
+}
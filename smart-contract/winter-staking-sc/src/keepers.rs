@@ -0,0 +1,121 @@
+use multiversx_sc::imports::*;
+
+/// Highest keeper tip a user can grant, in basis points
+const MAX_KEEPER_TIP: u64 = 1_000;
+
+/// Module letting whitelisted keeper bots claim rewards on behalf of the users who approved them.
+#[multiversx_sc::module]
+pub trait KeepersModule {
+    /// Storage to hold the keepers whitelisted by the owner
+    #[storage_mapper("keepers")]
+    fn keepers(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    /// Storage to hold the keeper approved by each user
+    #[view(get_approved_keeper)]
+    #[storage_mapper("approved_keeper")]
+    fn approved_keeper(&self, user: &ManagedAddress) -> SingleValueMapper<ManagedAddress>;
+
+    /// Storage to hold the share of the claimed rewards each user grants to their keeper, in basis points
+    #[view(get_keeper_tip)]
+    #[storage_mapper("keeper_tip")]
+    fn keeper_tip(&self, user: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    /// Endpoint for the owner to whitelist a keeper
+    #[only_owner]
+    #[endpoint(add_keeper)]
+    fn add_keeper(&self, keeper: ManagedAddress) {
+        self.keepers().insert(keeper.clone());
+
+        // Emit an event for the keeper whitelisting
+        self.keeper_added_event(keeper);
+    }
+
+    /// Endpoint for the owner to remove a keeper from the whitelist.
+    /// Existing approvals stay stored but cannot be used until the keeper is whitelisted again.
+    #[only_owner]
+    #[endpoint(remove_keeper)]
+    fn remove_keeper(&self, keeper: ManagedAddress) {
+        require!(self.keepers().swap_remove(&keeper), "Keeper is not whitelisted");
+
+        // Emit an event for the keeper removal
+        self.keeper_removed_event(keeper);
+    }
+
+    /// Endpoint to let a whitelisted keeper claim the rewards of the caller for a tip in basis points
+    #[endpoint(approve_keeper)]
+    fn approve_keeper(&self, keeper: ManagedAddress, tip: u64) {
+        require!(self.keepers().contains(&keeper), "Keeper is not whitelisted");
+        require!(tip <= MAX_KEEPER_TIP, "Keeper tip exceeds the maximum");
+
+        let caller = self.blockchain().get_caller();
+        self.approved_keeper(&caller).set(&keeper);
+        self.keeper_tip(&caller).set(tip);
+
+        // Emit an event for the keeper approval
+        self.keeper_approved_event(caller, keeper, tip);
+    }
+
+    /// Endpoint to withdraw the approval given to a keeper
+    #[endpoint(revoke_keeper)]
+    fn revoke_keeper(&self) {
+        let caller = self.blockchain().get_caller();
+        require!(!self.approved_keeper(&caller).is_empty(), "No keeper approved");
+
+        self.approved_keeper(&caller).clear();
+        self.keeper_tip(&caller).clear();
+
+        // Emit an event for the keeper revocation
+        self.keeper_revoked_event(caller);
+    }
+
+    /// View endpoint returning the whitelisted keepers
+    #[view(get_keepers)]
+    fn get_keepers(&self) -> MultiValueEncoded<ManagedAddress> {
+        let mut result = MultiValueEncoded::new();
+
+        for keeper in self.keepers().iter() {
+            result.push(keeper);
+        }
+
+        result
+    }
+
+    /// Ensures the keeper is whitelisted and approved by the user
+    fn require_approved_keeper(&self, user: &ManagedAddress, keeper: &ManagedAddress) {
+        require!(self.keepers().contains(keeper), "Keeper is not whitelisted");
+        require!(
+            !self.approved_keeper(user).is_empty() && &self.approved_keeper(user).get() == keeper,
+            "Keeper is not approved by the user"
+        );
+    }
+
+    /// Emit an event for whitelisting a keeper
+    #[event("keeper_added_event")]
+    fn keeper_added_event(&self, #[indexed] keeper: ManagedAddress);
+
+    /// Emit an event for removing a keeper from the whitelist
+    #[event("keeper_removed_event")]
+    fn keeper_removed_event(&self, #[indexed] keeper: ManagedAddress);
+
+    /// Emit an event for a user approving a keeper
+    #[event("keeper_approved_event")]
+    fn keeper_approved_event(
+        &self,
+        #[indexed] user: ManagedAddress,
+        #[indexed] keeper: ManagedAddress,
+        #[indexed] tip: u64,
+    );
+
+    /// Emit an event for a user revoking their keeper
+    #[event("keeper_revoked_event")]
+    fn keeper_revoked_event(&self, #[indexed] user: ManagedAddress);
+
+    /// Emit an event for a keeper claiming the rewards of a user
+    #[event("keeper_claim_event")]
+    fn keeper_claim_event(
+        &self,
+        #[indexed] keeper: ManagedAddress,
+        #[indexed] user: ManagedAddress,
+        tip_data: (TokenIdentifier, BigUint),
+    );
+}
@@ -0,0 +1,84 @@
+use multiversx_sc::imports::*;
+
+/// Module letting the owner stop the contract. While paused, staking and reward claims are blocked.
+/// Once the emergency mode is enabled, the contract stays paused and users can only take their
+/// principal back, without rewards.
+#[multiversx_sc::module]
+pub trait PauseModule {
+    /// Storage to hold whether staking and reward claims are blocked
+    #[view(is_paused)]
+    #[storage_mapper("paused")]
+    fn paused(&self) -> SingleValueMapper<bool>;
+
+    /// Storage to hold whether users can withdraw their principal through `emergency_withdraw`
+    #[view(is_emergency_mode)]
+    #[storage_mapper("emergency_mode")]
+    fn emergency_mode(&self) -> SingleValueMapper<bool>;
+
+    /// Endpoint for the owner to block staking and reward claims
+    #[only_owner]
+    #[endpoint(pause)]
+    fn pause(&self) {
+        require!(!self.paused().get(), "Contract is already paused");
+        self.paused().set(true);
+
+        // Emit an event for pausing the contract
+        self.paused_event();
+    }
+
+    /// Endpoint for the owner to resume staking and reward claims, not possible in emergency mode
+    #[only_owner]
+    #[endpoint(unpause)]
+    fn unpause(&self) {
+        require!(self.paused().get(), "Contract is not paused");
+        require!(!self.emergency_mode().get(), "Contract cannot be unpaused in emergency mode");
+        self.paused().set(false);
+
+        // Emit an event for unpausing the contract
+        self.unpaused_event();
+    }
+
+    /// Endpoint for the owner to let users withdraw their principal without rewards.
+    /// The contract must be paused first and stays paused for good.
+    #[only_owner]
+    #[endpoint(enable_emergency_mode)]
+    fn enable_emergency_mode(&self) {
+        require!(self.paused().get(), "Contract must be paused first");
+        require!(!self.emergency_mode().get(), "Emergency mode is already enabled");
+        self.emergency_mode().set(true);
+
+        // Emit an event for enabling the emergency mode
+        self.emergency_mode_enabled_event();
+    }
+
+    /// Ensures staking and reward claims are not blocked
+    fn require_not_paused(&self) {
+        require!(!self.paused().get(), "Contract is paused");
+    }
+
+    /// Ensures the emergency mode is enabled
+    fn require_emergency_mode(&self) {
+        require!(self.emergency_mode().get(), "Emergency mode is not enabled");
+    }
+
+    /// Emit an event for pausing the contract
+    #[event("paused_event")]
+    fn paused_event(&self);
+
+    /// Emit an event for unpausing the contract
+    #[event("unpaused_event")]
+    fn unpaused_event(&self);
+
+    /// Emit an event for enabling the emergency mode
+    #[event("emergency_mode_enabled_event")]
+    fn emergency_mode_enabled_event(&self);
+
+    /// Emit an event for withdrawing the principal of a position in emergency mode
+    #[event("emergency_withdraw_event")]
+    fn emergency_withdraw_event(
+        &self,
+        #[indexed] user: ManagedAddress,
+        #[indexed] position_id: u64,
+        withdraw_data: (TokenIdentifier, BigUint),
+    );
+}
@@ -0,0 +1,373 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+use crate::staking_tokens::MULTIPLIER_DENOMINATOR;
+
+/// Scaling factor of the redistributed penalty accumulators
+const PENALTY_PRECISION: u64 = 1_000_000_000_000_000_000;
+
+/// Maximum early-exit penalty accepted, in basis points (50%)
+const MAX_EARLY_EXIT_PENALTY: u64 = 5_000;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct StakePosition<M: ManagedTypeApi> {
+    pub owner: ManagedAddress<M>,
+    pub token_identifier: TokenIdentifier<M>,
+    pub amount: BigUint<M>,
+    pub shares: BigUint<M>,
+    pub lock_epochs: u64,
+    pub start_epoch: u64,
+    pub unlock_epoch: u64,
+    pub penalty_per_share: BigUint<M>,
+}
+
+/// Attributes of the Meta-ESDT receipt representing a position
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct PositionAttributes<M: ManagedTypeApi> {
+    pub token_identifier: TokenIdentifier<M>,
+    pub amount: BigUint<M>,
+    pub start_epoch: u64,
+    pub lock_epochs: u64,
+}
+
+/// Module keeping each stake as a position locked for the lock tier chosen at stake time.
+/// Each position is represented by a Meta-ESDT receipt whose nonce is the position id.
+/// Positions exiting before their unlock epoch pay a penalty on the unstaked amount.
+#[multiversx_sc::module]
+pub trait PositionsModule {
+    /// Storage to hold the Meta-ESDT minted as position receipt
+    #[view(get_position_token)]
+    #[storage_mapper("position_token")]
+    fn position_token(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    /// Storage to hold each stake position by id
+    #[storage_mapper("stake_position")]
+    fn stake_position(&self, position_id: u64) -> SingleValueMapper<StakePosition<Self::Api>>;
+
+    /// Storage to track the positions registered to each user
+    #[storage_mapper("user_positions")]
+    fn user_positions(&self, user: &ManagedAddress) -> UnorderedSetMapper<u64>;
+
+    /// Storage to hold the lock durations, in epochs, that can be chosen at stake time
+    #[storage_mapper("lock_tiers")]
+    fn lock_tiers(&self) -> UnorderedSetMapper<u64>;
+
+    /// Storage to hold the reward multiplier of each lock tier, in basis points
+    #[storage_mapper("lock_tier_multiplier")]
+    fn lock_tier_multiplier(&self, lock_epochs: u64) -> SingleValueMapper<u64>;
+
+    /// Storage to hold the penalty paid on amounts unstaked before the unlock epoch, in basis points
+    #[view(get_early_exit_penalty)]
+    #[storage_mapper("early_exit_penalty")]
+    fn early_exit_penalty(&self) -> SingleValueMapper<u64>;
+
+    /// Storage to hold the address receiving early-exit penalties, when empty they are
+    /// redistributed to the remaining stakers of the token
+    #[view(get_penalty_treasury)]
+    #[storage_mapper("penalty_treasury")]
+    fn penalty_treasury(&self) -> SingleValueMapper<ManagedAddress>;
+
+    /// Storage to hold the amount held by all positions of each token
+    #[view(getTotalStaked)]
+    #[storage_mapper("total_staked")]
+    fn total_staked(&self, token: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    /// Storage to hold the redistributed penalties per staked unit of each token, scaled by `PENALTY_PRECISION`
+    #[storage_mapper("penalty_per_share")]
+    fn penalty_per_share(&self, token: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    /// Storage to hold the early-exit penalties of each token that could not be redistributed because
+    /// no other position was left, they are added to the next redistributed penalty
+    #[view(get_undistributed_penalty)]
+    #[storage_mapper("undistributed_penalty")]
+    fn undistributed_penalty(&self, token: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    /// Endpoint for the owner to issue the position receipt token, the contract receives all its roles
+    #[only_owner]
+    #[payable("EGLD")]
+    #[endpoint(issue_position_token)]
+    fn issue_position_token(&self, token_name: ManagedBuffer, token_ticker: ManagedBuffer) {
+        require!(self.position_token().is_empty(), "Position token already issued");
+
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().egld_value().clone_value();
+
+        self.send()
+            .esdt_system_sc_proxy()
+            .issue_and_set_all_roles(
+                payment.clone(),
+                token_name,
+                token_ticker,
+                EsdtTokenType::Meta,
+                0,
+            )
+            .with_callback(self.callbacks().position_token_issue_callback(caller, payment))
+            .async_call_and_exit();
+    }
+
+    /// Callback for issuing the position receipt token
+    #[callback]
+    fn position_token_issue_callback(
+        &self,
+        caller: ManagedAddress,
+        payment: BigUint,
+        #[call_result] result: ManagedAsyncCallResult<TokenIdentifier>,
+    ) {
+        match result {
+            ManagedAsyncCallResult::Ok(token_identifier) => {
+                self.position_token().set(&token_identifier);
+
+                // Emit an event for the position token issuance
+                self.position_token_issued_event(token_identifier);
+            }
+            ManagedAsyncCallResult::Err(_) => {
+                // Refund the issuance cost paid by the owner
+                if payment > 0 {
+                    self.tx().to(&caller).egld(&payment).transfer();
+                }
+            }
+        }
+    }
+
+    /// Endpoint for the owner to add a lock tier or change its reward multiplier
+    #[only_owner]
+    #[endpoint(set_lock_tier)]
+    fn set_lock_tier(&self, lock_epochs: u64, multiplier: u64) {
+        require!(
+            multiplier >= MULTIPLIER_DENOMINATOR,
+            "Lock tier multiplier must be at least 10000 basis points"
+        );
+
+        self.lock_tiers().insert(lock_epochs);
+        self.lock_tier_multiplier(lock_epochs).set(multiplier);
+
+        // Emit an event for the lock tier update
+        self.lock_tier_set_event(lock_epochs, multiplier);
+    }
+
+    /// Endpoint for the owner to remove a lock tier, existing positions keep their lock and multiplier
+    #[only_owner]
+    #[endpoint(remove_lock_tier)]
+    fn remove_lock_tier(&self, lock_epochs: u64) {
+        require!(self.lock_tiers().swap_remove(&lock_epochs), "Unknown lock tier");
+        self.lock_tier_multiplier(lock_epochs).clear();
+
+        // Emit an event for the lock tier update
+        self.lock_tier_removed_event(lock_epochs);
+    }
+
+    /// Endpoint for the owner to set the early-exit penalty, in basis points
+    #[only_owner]
+    #[endpoint(set_early_exit_penalty)]
+    fn set_early_exit_penalty(&self, penalty: u64) {
+        require!(
+            penalty <= MAX_EARLY_EXIT_PENALTY,
+            "Early-exit penalty must be at most 5000 basis points"
+        );
+
+        self.early_exit_penalty().set(penalty);
+    }
+
+    /// Endpoint for the owner to send early-exit penalties to a treasury instead of redistributing them
+    #[only_owner]
+    #[endpoint(set_penalty_treasury)]
+    fn set_penalty_treasury(&self, treasury: ManagedAddress) {
+        self.penalty_treasury().set(treasury);
+    }
+
+    /// Endpoint for the owner to redistribute early-exit penalties to the remaining stakers again
+    #[only_owner]
+    #[endpoint(clear_penalty_treasury)]
+    fn clear_penalty_treasury(&self) {
+        self.penalty_treasury().clear();
+    }
+
+    /// View endpoint to list the lock tiers with their reward multiplier
+    #[view(get_lock_tiers)]
+    fn get_lock_tiers(&self) -> MultiValueEncoded<MultiValue2<u64, u64>> {
+        let mut result = MultiValueEncoded::new();
+
+        for lock_epochs in self.lock_tiers().iter() {
+            let multiplier = self.lock_tier_multiplier(lock_epochs).get();
+            result.push((lock_epochs, multiplier).into());
+        }
+
+        result
+    }
+
+    /// View endpoint to query the positions registered to a user with their id
+    #[view(get_user_positions)]
+    fn get_user_positions(
+        &self,
+        user: ManagedAddress,
+    ) -> MultiValueEncoded<MultiValue2<u64, StakePosition<Self::Api>>> {
+        let mut result = MultiValueEncoded::new();
+
+        for position_id in self.user_positions(&user).iter() {
+            result.push((position_id, self.stake_position(position_id).get()).into());
+        }
+
+        result
+    }
+
+    /// Returns the reward multiplier of a lock tier, staking without lock is always possible
+    fn lock_multiplier(&self, lock_epochs: u64) -> u64 {
+        if self.lock_tiers().contains(&lock_epochs) {
+            return self.lock_tier_multiplier(lock_epochs).get();
+        }
+
+        require!(lock_epochs == 0, "Unknown lock tier");
+        MULTIPLIER_DENOMINATOR
+    }
+
+    /// Stores a new position, mints its receipt and returns its id
+    fn create_position(
+        &self,
+        owner: &ManagedAddress,
+        token: &TokenIdentifier,
+        amount: &BigUint,
+        shares: BigUint,
+        lock_epochs: u64,
+    ) -> u64 {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let position = StakePosition {
+            owner: owner.clone(),
+            token_identifier: token.clone(),
+            amount: amount.clone(),
+            shares,
+            lock_epochs,
+            start_epoch: current_epoch,
+            unlock_epoch: current_epoch + lock_epochs,
+            penalty_per_share: self.penalty_per_share(token).get(),
+        };
+
+        self.total_staked(token).update(|total| *total += amount);
+
+        self.store_position(position)
+    }
+
+    /// Mints a receipt for a position and stores the position under the receipt nonce
+    fn store_position(&self, position: StakePosition<Self::Api>) -> u64 {
+        require!(!self.position_token().is_empty(), "Position token is not issued");
+
+        let attributes = PositionAttributes {
+            token_identifier: position.token_identifier.clone(),
+            amount: position.amount.clone(),
+            start_epoch: position.start_epoch,
+            lock_epochs: position.lock_epochs,
+        };
+        let position_id = self.send().esdt_nft_create_compact(
+            &self.position_token().get(),
+            &BigUint::from(1u32),
+            &attributes,
+        );
+
+        self.user_positions(&position.owner).insert(position_id);
+        self.stake_position(position_id).set(position);
+
+        position_id
+    }
+
+    /// Removes a position and burns its receipt, which must be held by the contract
+    fn remove_position(&self, position_id: u64, owner: &ManagedAddress) {
+        self.stake_position(position_id).clear();
+        self.user_positions(owner).swap_remove(&position_id);
+
+        self.send().esdt_local_burn(
+            &self.position_token().get(),
+            position_id,
+            &BigUint::from(1u32),
+        );
+    }
+
+    /// Ensures a payment is a position receipt and returns the position id
+    fn require_position_receipt(&self, payment: &EsdtTokenPayment<Self::Api>) -> u64 {
+        require!(
+            !self.position_token().is_empty()
+                && payment.token_identifier == self.position_token().get(),
+            "Payment is not a position receipt"
+        );
+        require!(payment.amount == 1u64, "Invalid position receipt amount");
+        require!(!self.stake_position(payment.token_nonce).is_empty(), "Unknown position");
+
+        payment.token_nonce
+    }
+
+    /// Returns the penalties redistributed to a position since its last settlement and settles them
+    fn take_position_bonus(&self, position: &mut StakePosition<Self::Api>) -> BigUint {
+        let penalty_per_share = self.penalty_per_share(&position.token_identifier).get();
+        let accumulated = &penalty_per_share - &position.penalty_per_share;
+        let bonus = &position.amount * &accumulated / PENALTY_PRECISION;
+        position.penalty_per_share = penalty_per_share;
+
+        bonus
+    }
+
+    /// Charges the early-exit penalty on an amount unstaked from a position and returns it.
+    /// The unstaked amount must already be removed from `total_staked` and from the position.
+    /// Redistributed penalties only go to the other positions of the token.
+    fn charge_early_exit_penalty(
+        &self,
+        position_id: u64,
+        position: &StakePosition<Self::Api>,
+        amount: &BigUint,
+    ) -> BigUint {
+        if self.blockchain().get_block_epoch() >= position.unlock_epoch {
+            return BigUint::zero();
+        }
+
+        let penalty = amount.clone() * self.early_exit_penalty().get() / MULTIPLIER_DENOMINATOR;
+        if penalty == 0 {
+            return penalty;
+        }
+
+        let token = &position.token_identifier;
+        if !self.penalty_treasury().is_empty() {
+            self.send().direct_esdt(&self.penalty_treasury().get(), token, 0, &penalty);
+        } else {
+            let others_staked = self.total_staked(token).get() - &position.amount;
+            if others_staked == 0 {
+                // Without other positions the penalty is kept for the next redistribution
+                self.undistributed_penalty(token)
+                    .update(|undistributed| *undistributed += &penalty);
+            } else {
+                let redistributed = penalty.clone() + self.undistributed_penalty(token).take();
+                self.penalty_per_share(token).update(|penalty_per_share| {
+                    *penalty_per_share += redistributed * PENALTY_PRECISION / others_staked
+                });
+            }
+        }
+
+        // Emit early-exit penalty event
+        self.early_exit_penalty_event(
+            position.owner.clone(),
+            position_id,
+            (token.clone(), penalty.clone()),
+        );
+
+        penalty
+    }
+
+    /// Emit an event for issuing the position receipt token
+    #[event("position_token_issued_event")]
+    fn position_token_issued_event(&self, #[indexed] token_identifier: TokenIdentifier);
+
+    /// Emit an event for adding or updating a lock tier
+    #[event("lock_tier_set_event")]
+    fn lock_tier_set_event(&self, #[indexed] lock_epochs: u64, #[indexed] multiplier: u64);
+
+    /// Emit an event for removing a lock tier
+    #[event("lock_tier_removed_event")]
+    fn lock_tier_removed_event(&self, #[indexed] lock_epochs: u64);
+
+    /// Emit an event for charging an early-exit penalty
+    #[event("early_exit_penalty_event")]
+    fn early_exit_penalty_event(
+        &self,
+        #[indexed] user: ManagedAddress,
+        #[indexed] position_id: u64,
+        penalty_data: (TokenIdentifier, BigUint),
+    );
+}
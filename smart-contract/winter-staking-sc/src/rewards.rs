@@ -0,0 +1,204 @@
+use multiversx_sc::imports::*;
+
+use crate::emission;
+use crate::staking_tokens::MULTIPLIER_DENOMINATOR;
+
+/// Scaling factor of the reward per share accumulator
+const REWARD_PRECISION: u64 = 1_000_000_000_000_000_000;
+
+/// Number of seconds in a year, used to annualise the emission
+const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// Module accruing rewards proportionally to the staked amount and the time it stays staked.
+/// The tokens emitted by the emission schedule are shared between all staked shares.
+#[multiversx_sc::module]
+pub trait RewardsModule: emission::EmissionModule {
+    /// Storage to hold the rewards accumulated per staked share, scaled by `REWARD_PRECISION`
+    #[storage_mapper("reward_per_share")]
+    fn reward_per_share(&self) -> SingleValueMapper<BigUint>;
+
+    /// Storage to hold the timestamp up to which `reward_per_share` was accumulated
+    #[storage_mapper("last_reward_timestamp")]
+    fn last_reward_timestamp(&self) -> SingleValueMapper<u64>;
+
+    /// Storage to hold the total number of shares earning rewards
+    #[storage_mapper("total_reward_shares")]
+    fn total_reward_shares(&self) -> SingleValueMapper<BigUint>;
+
+    /// Storage to hold the number of shares earning rewards for each user
+    #[storage_mapper("user_reward_shares")]
+    fn user_reward_shares(&self, user: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    /// Storage to hold the value of `reward_per_share` when the rewards of each user were last settled
+    #[storage_mapper("user_reward_per_share")]
+    fn user_reward_per_share(&self, user: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    /// Storage to hold the settled but unclaimed rewards of each user
+    #[storage_mapper("user_accrued_rewards")]
+    fn user_accrued_rewards(&self, user: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    /// Endpoint for the owner to set the emission schedule, starting a new first period now.
+    /// Without halvings, `halving_periods` is zero.
+    #[only_owner]
+    #[endpoint(set_emission_schedule)]
+    fn set_emission_schedule(
+        &self,
+        period_duration: u64,
+        initial_emission: BigUint,
+        halving_periods: u64,
+        emission_cap: BigUint,
+    ) {
+        // Rewards accumulated so far are computed with the previous schedule
+        self.update_reward_per_share();
+        self.store_emission_schedule(
+            period_duration,
+            initial_emission.clone(),
+            halving_periods,
+            emission_cap.clone(),
+        );
+
+        // Emit an event for the emission schedule update
+        self.emission_schedule_set_event(
+            period_duration,
+            halving_periods,
+            initial_emission,
+            emission_cap,
+        );
+    }
+
+    /// Endpoint for the owner to set the emission of each period, replacing the halvings.
+    /// The last step applies to all following periods, an empty table restores the halvings.
+    #[only_owner]
+    #[endpoint(set_emission_steps)]
+    fn set_emission_steps(&self, steps: MultiValueEncoded<BigUint>) {
+        // Rewards accumulated so far are computed with the previous steps
+        self.update_reward_per_share();
+
+        self.emission_steps().clear();
+        for step in steps.into_iter() {
+            self.emission_steps().push(&step);
+        }
+
+        // Emit an event for the emission steps update
+        self.emission_steps_set_event(self.emission_steps().len());
+    }
+
+    /// View endpoint returning the yearly rewards per staked share at the current emission, in basis points.
+    /// For a token without multiplier or lock boost, this is the APR when the reward and staked tokens
+    /// have the same value.
+    #[view(getApr)]
+    fn get_apr(&self) -> BigUint {
+        let total_shares = self.total_reward_shares().get();
+        if total_shares == 0 || self.emission_schedule().is_empty() {
+            return BigUint::zero();
+        }
+
+        let schedule = self.emission_schedule().get();
+        if self.total_emitted().get() >= schedule.emission_cap {
+            return BigUint::zero();
+        }
+
+        let period_emission = self.period_emission(&schedule, self.current_period(&schedule));
+        let yearly_emission = period_emission * SECONDS_PER_YEAR / schedule.period_duration;
+
+        yearly_emission * MULTIPLIER_DENOMINATOR / total_shares
+    }
+
+    /// Accumulates the rewards distributed since the last update into `reward_per_share`
+    fn update_reward_per_share(&self) {
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        let last_timestamp = self.last_reward_timestamp().get();
+        if current_timestamp <= last_timestamp {
+            return;
+        }
+
+        let (emission, reached_timestamp) = self.compute_emission(last_timestamp, current_timestamp);
+
+        // Without stakers, the emission of the interval is not distributed and does not count towards the cap
+        let total_shares = self.total_reward_shares().get();
+        if total_shares > 0 && emission > 0 {
+            let distributed = self.record_emission(emission);
+            self.reward_per_share()
+                .update(|rps| *rps += distributed * REWARD_PRECISION / total_shares);
+        }
+
+        self.last_reward_timestamp().set(reached_timestamp);
+    }
+
+    /// Returns the value `reward_per_share` would have after an update at the current timestamp
+    fn current_reward_per_share(&self) -> BigUint {
+        let reward_per_share = self.reward_per_share().get();
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        let last_timestamp = self.last_reward_timestamp().get();
+        let total_shares = self.total_reward_shares().get();
+        if current_timestamp <= last_timestamp || total_shares == 0 {
+            return reward_per_share;
+        }
+
+        let (emission, _) = self.compute_emission(last_timestamp, current_timestamp);
+        if emission == 0 {
+            return reward_per_share;
+        }
+
+        reward_per_share + self.capped_emission(emission) * REWARD_PRECISION / total_shares
+    }
+
+    /// Returns the settled and not yet settled rewards of a user, without changing any storage
+    fn pending_rewards(&self, user: &ManagedAddress) -> BigUint {
+        let reward_per_share = self.current_reward_per_share();
+        self.user_accrued_rewards(user).get() + self.compute_earned_rewards(user, &reward_per_share)
+    }
+
+    /// Moves the rewards earned by a user since the last settlement into the accrued rewards
+    fn settle_user_rewards(&self, user: &ManagedAddress) {
+        self.update_reward_per_share();
+
+        let reward_per_share = self.reward_per_share().get();
+        let earned = self.compute_earned_rewards(user, &reward_per_share);
+        if earned > 0 {
+            self.user_accrued_rewards(user).update(|accrued| *accrued += earned);
+        }
+
+        self.user_reward_per_share(user).set(reward_per_share);
+    }
+
+    /// Computes the rewards earned by a user between the last settlement and the given accumulator value
+    fn compute_earned_rewards(&self, user: &ManagedAddress, reward_per_share: &BigUint) -> BigUint {
+        let user_reward_per_share = self.user_reward_per_share(user).get();
+        let shares = self.user_reward_shares(user).get();
+
+        shares * &(reward_per_share - &user_reward_per_share) / REWARD_PRECISION
+    }
+
+    /// Adds reward shares to a user, its rewards must be settled beforehand
+    fn add_reward_shares(&self, user: &ManagedAddress, shares: &BigUint) {
+        self.user_reward_shares(user).update(|user_shares| *user_shares += shares);
+        self.total_reward_shares().update(|total| *total += shares);
+    }
+
+    /// Removes reward shares from a user, its rewards must be settled beforehand
+    fn remove_reward_shares(&self, user: &ManagedAddress, shares: &BigUint) {
+        self.user_reward_shares(user).update(|user_shares| *user_shares -= shares);
+        self.total_reward_shares().update(|total| *total -= shares);
+    }
+
+    /// Settles and returns the unclaimed rewards of a user, resetting them to zero
+    fn take_user_rewards(&self, user: &ManagedAddress) -> BigUint {
+        self.settle_user_rewards(user);
+        self.user_accrued_rewards(user).take()
+    }
+
+    /// Emit an event for setting the emission schedule
+    #[event("emission_schedule_set_event")]
+    fn emission_schedule_set_event(
+        &self,
+        #[indexed] period_duration: u64,
+        #[indexed] halving_periods: u64,
+        #[indexed] initial_emission: BigUint,
+        emission_cap: BigUint,
+    );
+
+    /// Emit an event for setting the emission steps
+    #[event("emission_steps_set_event")]
+    fn emission_steps_set_event(&self, #[indexed] steps_count: usize);
+}
@@ -0,0 +1,84 @@
+use multiversx_sc::imports::*;
+
+/// Denominator of the reward multipliers, expressed in basis points (10000 = 1x)
+pub const MULTIPLIER_DENOMINATOR: u64 = 10_000;
+
+/// Module holding the owner-managed allow-list of tokens accepted for staking.
+#[multiversx_sc::module]
+pub trait StakingTokensModule {
+    /// Storage to hold the tokens accepted for staking
+    #[storage_mapper("staking_tokens")]
+    fn staking_tokens(&self) -> UnorderedSetMapper<TokenIdentifier>;
+
+    /// Storage to hold the reward multiplier of each accepted token, in basis points
+    #[view(get_token_multiplier)]
+    #[storage_mapper("token_multiplier")]
+    fn token_multiplier(&self, token: &TokenIdentifier) -> SingleValueMapper<u64>;
+
+    /// Endpoint for the owner to accept a token for staking, or change its reward multiplier
+    #[only_owner]
+    #[endpoint(add_staking_token)]
+    fn add_staking_token(&self, token: TokenIdentifier, multiplier: u64) {
+        require!(token.is_valid_esdt_identifier(), "Invalid token identifier");
+        require!(multiplier > 0, "Reward multiplier must be greater than zero");
+
+        self.staking_tokens().insert(token.clone());
+        self.token_multiplier(&token).set(multiplier);
+
+        // Emit an event for the allow-list update
+        self.staking_token_added_event(token, multiplier);
+    }
+
+    /// Endpoint for the owner to stop accepting a token for staking.
+    /// Existing stakes keep earning rewards and can still be unstaked.
+    #[only_owner]
+    #[endpoint(remove_staking_token)]
+    fn remove_staking_token(&self, token: TokenIdentifier) {
+        require!(
+            self.staking_tokens().swap_remove(&token),
+            "Token is not accepted for staking"
+        );
+        self.token_multiplier(&token).clear();
+
+        // Emit an event for the allow-list update
+        self.staking_token_removed_event(token);
+    }
+
+    /// View endpoint to list the tokens accepted for staking with their reward multiplier
+    #[view(get_staking_tokens)]
+    fn get_staking_tokens(&self) -> MultiValueEncoded<MultiValue2<TokenIdentifier, u64>> {
+        let mut result = MultiValueEncoded::new();
+
+        for token in self.staking_tokens().iter() {
+            let multiplier = self.token_multiplier(&token).get();
+            result.push((token, multiplier).into());
+        }
+
+        result
+    }
+
+    /// Ensures a token is accepted for staking
+    fn require_staking_token(&self, token: &TokenIdentifier) {
+        require!(
+            self.staking_tokens().contains(token),
+            "Token is not accepted for staking"
+        );
+    }
+
+    /// Computes the reward shares of an amount of an accepted token
+    fn compute_stake_shares(&self, token: &TokenIdentifier, amount: &BigUint) -> BigUint {
+        amount.clone() * self.token_multiplier(token).get() / MULTIPLIER_DENOMINATOR
+    }
+
+    /// Emit an event for accepting a token for staking
+    #[event("staking_token_added_event")]
+    fn staking_token_added_event(
+        &self,
+        #[indexed] token: TokenIdentifier,
+        #[indexed] multiplier: u64,
+    );
+
+    /// Emit an event for removing a token from the staking allow-list
+    #[event("staking_token_removed_event")]
+    fn staking_token_removed_event(&self, #[indexed] token: TokenIdentifier);
+}
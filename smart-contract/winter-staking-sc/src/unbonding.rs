@@ -0,0 +1,129 @@
+use multiversx_sc::derive_imports::*;
+use multiversx_sc::imports::*;
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode)]
+pub struct UnbondingEntry<M: ManagedTypeApi> {
+    pub token_identifier: TokenIdentifier<M>,
+    pub amount: BigUint<M>,
+    pub unlock_epoch: u64,
+}
+
+/// Module keeping unstaked tokens locked for the unbonding period before they can be withdrawn.
+#[multiversx_sc::module]
+pub trait UnbondingModule {
+    /// Storage to hold the number of epochs unstaked tokens stay locked
+    #[view(get_unbonding_period)]
+    #[storage_mapper("unbonding_period")]
+    fn unbonding_period(&self) -> SingleValueMapper<u64>;
+
+    /// Storage to hold each unbonding entry by id
+    #[storage_mapper("unbonding_entry")]
+    fn unbonding_entry(&self, unbonding_id: u64) -> SingleValueMapper<UnbondingEntry<Self::Api>>;
+
+    /// Storage to track the unbonding entries of each user
+    #[storage_mapper("user_unbondings")]
+    fn user_unbondings(&self, user: &ManagedAddress) -> UnorderedSetMapper<u64>;
+
+    /// Storage to hold the id of the last created unbonding entry
+    #[storage_mapper("last_unbonding_id")]
+    fn last_unbonding_id(&self) -> SingleValueMapper<u64>;
+
+    /// Endpoint for the owner to set the unbonding period in epochs
+    #[only_owner]
+    #[endpoint(set_unbonding_period)]
+    fn set_unbonding_period(&self, epochs: u64) {
+        self.unbonding_period().set(epochs);
+
+        // Emit an event for the unbonding period update
+        self.unbonding_period_set_event(epochs);
+    }
+
+    /// Endpoint to withdraw all unbonding entries whose unbonding period has passed
+    #[endpoint(withdraw)]
+    fn withdraw(&self) {
+        let caller = self.blockchain().get_caller();
+        let current_epoch = self.blockchain().get_block_epoch();
+
+        // Collect the matured entries first, the set cannot be changed while iterating it
+        let mut matured_ids = ManagedVec::<Self::Api, u64>::new();
+        for unbonding_id in self.user_unbondings(&caller).iter() {
+            if self.unbonding_entry(unbonding_id).get().unlock_epoch <= current_epoch {
+                matured_ids.push(unbonding_id);
+            }
+        }
+
+        require!(!matured_ids.is_empty(), "No unbonded tokens available to withdraw");
+
+        for unbonding_id in matured_ids.iter() {
+            let entry = self.unbonding_entry(unbonding_id).take();
+            self.user_unbondings(&caller).swap_remove(&unbonding_id);
+
+            self.send()
+                .direct_esdt(&caller, &entry.token_identifier, 0, &entry.amount);
+
+            // Emit withdraw event
+            self.withdraw_event(caller.clone(), entry.token_identifier, entry.amount);
+        }
+    }
+
+    /// View endpoint to query the unbonding entries of a user that were not withdrawn yet
+    #[view(get_pending_unbonds)]
+    fn get_pending_unbonds(
+        &self,
+        user: ManagedAddress,
+    ) -> MultiValueEncoded<UnbondingEntry<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+
+        for unbonding_id in self.user_unbondings(&user).iter() {
+            result.push(self.unbonding_entry(unbonding_id).get());
+        }
+
+        result
+    }
+
+    /// Stores a new unbonding entry that matures after the unbonding period
+    fn add_unbonding_entry(
+        &self,
+        user: &ManagedAddress,
+        token_identifier: TokenIdentifier,
+        amount: BigUint,
+    ) {
+        let unlock_epoch = self.blockchain().get_block_epoch() + self.unbonding_period().get();
+
+        let unbonding_id = self.last_unbonding_id().get() + 1;
+        self.last_unbonding_id().set(unbonding_id);
+
+        self.user_unbondings(user).insert(unbonding_id);
+        self.unbonding_entry(unbonding_id).set(UnbondingEntry {
+            token_identifier: token_identifier.clone(),
+            amount: amount.clone(),
+            unlock_epoch,
+        });
+
+        // Emit unstake event
+        self.unstake_event(user.clone(), unlock_epoch, (token_identifier, amount));
+    }
+
+    /// Emit an event for unstaking
+    #[event("unstake_event")]
+    fn unstake_event(
+        &self,
+        #[indexed] user: ManagedAddress,
+        #[indexed] unlock_epoch: u64,
+        unstake_data: (TokenIdentifier, BigUint),
+    );
+
+    /// Emit an event for withdrawing unbonded tokens
+    #[event("withdraw_event")]
+    fn withdraw_event(
+        &self,
+        #[indexed] user: ManagedAddress,
+        #[indexed] token_identifier: TokenIdentifier,
+        amount: BigUint,
+    );
+
+    /// Emit an event for setting the unbonding period
+    #[event("unbonding_period_set_event")]
+    fn unbonding_period_set_event(&self, #[indexed] epochs: u64);
+}
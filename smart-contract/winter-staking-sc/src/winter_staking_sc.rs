@@ -1,12 +1,76 @@
 #![no_std]
 use multiversx_sc::imports::*;
 
+use staking_tokens::MULTIPLIER_DENOMINATOR;
+
+/// Minimum time between two reward claims of a user, in seconds
+const CLAIM_INTERVAL: u64 = 24 * 60 * 60;
+
+pub mod beneficiary;
+pub mod compounding;
+pub mod emission;
+pub mod keepers;
+pub mod pause;
+pub mod positions;
+pub mod rewards;
+pub mod staking_tokens;
+pub mod unbonding;
+
 #[multiversx_sc::contract]
-pub trait WinterStakingSc {
+pub trait WinterStakingSc:
+    beneficiary::BeneficiaryModule
+    + compounding::CompoundingModule
+    + emission::EmissionModule
+    + keepers::KeepersModule
+    + pause::PauseModule
+    + positions::PositionsModule
+    + rewards::RewardsModule
+    + staking_tokens::StakingTokensModule
+    + unbonding::UnbondingModule
+{
+    /// Initializes the contract, optionally with the reward token
     #[init]
-    fn init(&self) {}
+    fn init(&self, reward_token: OptionalValue<TokenIdentifier>) {
+        if let OptionalValue::Some(token) = reward_token {
+            self.set_reward_token_internal(token);
+        }
+    }
+
+    /// Storage to hold the token minted as staking reward
+    #[view(get_reward_token)]
+    #[storage_mapper("reward_token")]
+    fn reward_token(&self) -> SingleValueMapper<TokenIdentifier<Self::Api>>;
+
+    /// Endpoint for the owner to set or change the reward token
+    #[only_owner]
+    #[endpoint(set_reward_token)]
+    fn set_reward_token(&self, token: TokenIdentifier) {
+        self.set_reward_token_internal(token);
+    }
+
+    fn set_reward_token_internal(&self, token: TokenIdentifier) {
+        require!(token.is_valid_esdt_identifier(), "Invalid reward token identifier");
 
-    /// Storage to hold the staked tokens for each user
+        self.reward_token().set(&token);
+
+        // Emit an event for the reward token update
+        self.reward_token_set_event(token);
+    }
+
+    /// Ensures the reward token is set and the contract is allowed to mint it
+    fn require_reward_token_mintable(&self) {
+        require!(!self.reward_token().is_empty(), "Reward token is not set");
+
+        let roles = self
+            .blockchain()
+            .get_esdt_local_roles(&self.reward_token().get());
+        require!(
+            roles.has_role(&EsdtLocalRole::Mint),
+            "Contract does not have the local mint role for the reward token"
+        );
+    }
+
+    /// Legacy storage of the staked tokens for each user, kept until its entries are migrated
     #[storage_mapper("stakes")]
     fn stakes(
         &self,
@@ -15,6 +79,19 @@ pub trait WinterStakingSc {
         BigUint<Self::Api>,
     >;
 
+    /// Storage to track the tokens staked by each user
+    #[storage_mapper("user_staked_tokens")]
+    fn user_staked_tokens(&self, user: &ManagedAddress) -> UnorderedSetMapper<TokenIdentifier>;
+
+    /// Storage to hold the staked balance of each user in each token
+    #[storage_mapper("user_stake")]
+    fn user_stake(&self, user: &ManagedAddress, token: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    /// Storage to hold the number of users with a stake
+    #[view(getStakersCount)]
+    #[storage_mapper("stakers_count")]
+    fn stakers_count(&self) -> SingleValueMapper<usize>;
+
     /// Storage to track staking start epochs for each user
     #[storage_mapper("stake_start_epoch")]
     fn stake_start_epoch(&self, user: &ManagedAddress) -> SingleValueMapper<u64>;
@@ -23,33 +100,17 @@ pub trait WinterStakingSc {
     #[storage_mapper("last_reward_claim")]
     fn last_reward_claim(&self, user: &ManagedAddress) -> SingleValueMapper<u64>;
 
-    /// Storage to track the beneficiary for reward transfers
-    #[storage_mapper("beneficiary")]
-    fn beneficiary(&self, user: &ManagedAddress) -> SingleValueMapper<ManagedAddress<Self::Api>>;
-
-    /// Endpoint to set or update the beneficiary
-    #[endpoint(set_beneficiary)]
-    fn set_beneficiary(&self, new_beneficiary: ManagedAddress) {
-        let caller = self.blockchain().get_caller();
-
-        // Ensure the beneficiary address is valid and different from the caller
-        require!(
-            new_beneficiary != caller,
-            "Beneficiary cannot be the same as the caller"
-        );
-
-        self.beneficiary(&caller).set(new_beneficiary.clone());
-
-        // Emit an event for beneficiary update
-        self.beneficiary_set_event(caller, new_beneficiary);
-    }
-
-    /// Endpoint to stake WINTER tokens
+    /// Endpoint to stake WINTER tokens, optionally locked for one of the lock tiers
     #[payable("*")]
     #[endpoint(stake_token_winter)]
-    fn stake_token_winter(&self) {
+    fn stake_token_winter(&self, lock_epochs: OptionalValue<u64>) {
         let caller = self.blockchain().get_caller();
         let current_epoch = self.blockchain().get_block_epoch();
+        let lock_epochs = lock_epochs.into_option().unwrap_or_default();
+        self.require_not_paused();
+
+        // Rewards must be mintable before accepting any stake
+        self.require_reward_token_mintable();
 
         // Require at least one payment to stake
         require!(
@@ -57,31 +118,35 @@ pub trait WinterStakingSc {
             "At least one token must be staked"
         );
 
-        // Iterate over the tokens being staked
+        // Settle the rewards earned with the previous stake before changing it
+        self.settle_user_rewards(&caller);
+
+        // Iterate over the tokens being staked, each one is returned as a position receipt
+        let mut receipts = ManagedVec::new();
         for payment in self.call_value().all_esdt_transfers().iter() {
-            // Convert the token identifier to a byte slice
-            let token_bytes_boxed = payment.token_identifier.as_managed_buffer().to_boxed_bytes();
-            let token_bytes = token_bytes_boxed.as_ref();
+            // Only tokens from the allow-list can be staked
+            self.require_staking_token(&payment.token_identifier);
 
-            // Validate that the token identifier starts with "WINTER-"
-            let prefix = b"WINTER-";
-            require!(
-                token_bytes.starts_with(prefix),
-                "Only WINTER tokens can be staked"
-            );
-            
             require!(
                 payment.amount > 0,
                 "Token amount must be greater than zero"
             );
 
-            let key = (caller.clone(), payment.token_identifier.clone());
-            let current_stake = self.stakes().get(&key).unwrap_or_default();
-            let new_stake = current_stake + payment.amount.clone();
+            let position_id = self.add_stake(
+                &caller,
+                &payment.token_identifier,
+                &payment.amount,
+                lock_epochs,
+            );
 
-            self.stakes().insert(key, new_stake);
+            receipts.push(EsdtTokenPayment::new(
+                self.position_token().get(),
+                position_id,
+                BigUint::from(1u32),
+            ));
 
-            // Emit event
+            // Emit events
+            self.position_created_event(caller.clone(), position_id, lock_epochs);
             self.stake_event(
                 caller.clone(),
                 current_epoch,
@@ -96,8 +161,253 @@ pub trait WinterStakingSc {
 
         // Initialize the last reward claim time
         self.last_reward_claim(&caller).set_if_empty(self.blockchain().get_block_timestamp());
+
+        self.send().direct_multi(&caller, &receipts);
+    }
+
+    /// Adds a new position to the stake of a user and returns its id, its rewards must be settled
+    /// beforehand. The position earns rewards with both the token and the lock tier multipliers.
+    fn add_stake(
+        &self,
+        user: &ManagedAddress,
+        token: &TokenIdentifier,
+        amount: &BigUint,
+        lock_epochs: u64,
+    ) -> u64 {
+        self.increase_stake(user, token, amount);
+
+        let shares = self.compute_stake_shares(token, amount) * self.lock_multiplier(lock_epochs)
+            / MULTIPLIER_DENOMINATOR;
+        self.add_reward_shares(user, &shares);
+
+        self.create_position(user, token, amount, shares, lock_epochs)
+    }
+
+    /// Increases the staked balance of a user in a token
+    fn increase_stake(&self, user: &ManagedAddress, token: &TokenIdentifier, amount: &BigUint) {
+        if self.user_staked_tokens(user).is_empty() {
+            self.stakers_count().update(|count| *count += 1);
+        }
+        self.user_staked_tokens(user).insert(token.clone());
+        self.user_stake(user, token).update(|stake| *stake += amount);
+    }
+
+    /// Decreases the staked balance of a user in a token
+    fn decrease_stake(&self, user: &ManagedAddress, token: &TokenIdentifier, amount: &BigUint) {
+        let current_stake = self.user_stake(user, token).get();
+        require!(&current_stake >= amount, "Not enough tokens staked");
+
+        let new_stake = current_stake - amount;
+        if new_stake == 0 {
+            self.user_stake(user, token).clear();
+            self.user_staked_tokens(user).swap_remove(token);
+            if self.user_staked_tokens(user).is_empty() {
+                self.stakers_count().update(|count| *count -= 1);
+            }
+        } else {
+            self.user_stake(user, token).set(new_stake);
+        }
+    }
+
+    /// Endpoint for the owner to migrate up to `max_entries` legacy `stakes` entries.
+    /// Each entry becomes an unlocked position whose receipt is sent to the staker.
+    /// Returns the number of entries left to migrate.
+    #[only_owner]
+    #[endpoint(migrate_legacy_stakes)]
+    fn migrate_legacy_stakes(&self, max_entries: usize) -> usize {
+        let current_timestamp = self.blockchain().get_block_timestamp();
+
+        for _ in 0..max_entries {
+            let (key, amount) = match self.stakes().iter().next() {
+                Some(entry) => entry,
+                None => break,
+            };
+            self.stakes().remove(&key);
+
+            let (user, token) = key;
+            self.settle_user_rewards(&user);
+            let position_id = self.add_stake(&user, &token, &amount, 0);
+            self.send().direct_esdt(
+                &user,
+                &self.position_token().get(),
+                position_id,
+                &BigUint::from(1u32),
+            );
+
+            // Legacy stakers keep their claim timer
+            self.last_reward_claim(&user).set_if_empty(current_timestamp);
+
+            // Emit migration event
+            self.legacy_stake_migrated_event(user, position_id, (token, amount));
+        }
+
+        self.stakes().len()
+    }
+
+    /// Registers a position to the holder of its receipt, moving its stake and reward shares.
+    /// Rewards earned before the transfer stay with the previous owner.
+    fn transfer_position(&self, position_id: u64, new_owner: &ManagedAddress) {
+        let mut position = self.stake_position(position_id).get();
+        if &position.owner == new_owner {
+            return;
+        }
+
+        let previous_owner = position.owner.clone();
+        self.settle_user_rewards(&previous_owner);
+        self.settle_user_rewards(new_owner);
+
+        self.remove_reward_shares(&previous_owner, &position.shares);
+        self.add_reward_shares(new_owner, &position.shares);
+        self.decrease_stake(&previous_owner, &position.token_identifier, &position.amount);
+        self.increase_stake(new_owner, &position.token_identifier, &position.amount);
+
+        self.user_positions(&previous_owner).swap_remove(&position_id);
+        self.user_positions(new_owner).insert(position_id);
+        position.owner = new_owner.clone();
+        self.stake_position(position_id).set(position);
+
+        // Initialize the last reward claim time of a new staker
+        self.last_reward_claim(new_owner).set_if_empty(self.blockchain().get_block_timestamp());
+
+        // Emit position transfer event
+        self.position_transferred_event(previous_owner, new_owner.clone(), position_id);
+    }
+
+    /// Registers the position receipts sent with the call to the caller and returns them
+    fn register_received_positions(&self, caller: &ManagedAddress) {
+        let receipts = self.call_value().all_esdt_transfers().clone_value();
+        for receipt in receipts.iter() {
+            let position_id = self.require_position_receipt(&receipt);
+            self.transfer_position(position_id, caller);
+        }
+
+        if !receipts.is_empty() {
+            self.send().direct_multi(caller, &receipts);
+        }
+    }
+
+    /// Endpoint to register received position receipts to the caller, so they earn rewards for the caller
+    #[payable("*")]
+    #[endpoint(register_positions)]
+    fn register_positions(&self) {
+        let caller = self.blockchain().get_caller();
+
+        require!(
+            !self.call_value().all_esdt_transfers().is_empty(),
+            "At least one position must be sent"
+        );
+
+        self.register_received_positions(&caller);
     }
 
+    /// Endpoint to unstake an amount of the position sent as payment, which stops earning rewards and
+    /// unbonds for the unbonding period. Amounts unstaked before the unlock epoch pay the early-exit
+    /// penalty. The receipt of the remaining position, if any, is returned to the caller.
+    #[payable("*")]
+    #[endpoint(unstake)]
+    fn unstake(&self, amount: BigUint) {
+        let caller = self.blockchain().get_caller();
+        let receipt = self.call_value().single_esdt();
+        let position_id = self.require_position_receipt(&receipt);
+
+        require!(amount > 0, "Token amount must be greater than zero");
+
+        // A position received from another holder is registered to the caller first
+        self.transfer_position(position_id, &caller);
+
+        let mut position = self.stake_position(position_id).get();
+        require!(position.amount >= amount, "Not enough tokens staked");
+
+        // Settle the rewards earned so far, then remove the unstaked amount so it no longer earns rewards
+        self.settle_user_rewards(&caller);
+        let removed_shares = &position.shares * &amount / &position.amount;
+        self.remove_reward_shares(&caller, &removed_shares);
+
+        // Penalties redistributed to the position are paid out with the unstaked amount
+        let bonus = self.take_position_bonus(&mut position);
+
+        let token = position.token_identifier.clone();
+        self.decrease_stake(&caller, &token, &amount);
+
+        position.amount -= &amount;
+        position.shares -= &removed_shares;
+        self.total_staked(&token).update(|total| *total -= &amount);
+
+        let penalty = self.charge_early_exit_penalty(position_id, &position, &amount);
+
+        // The remaining position does not share the penalty it just paid
+        position.penalty_per_share = self.penalty_per_share(&token).get();
+
+        // The receipt encodes the amount, so the remaining position gets a new receipt
+        self.remove_position(position_id, &caller);
+        if position.amount > 0 {
+            let new_position_id = self.store_position(position);
+            self.send().direct_esdt(
+                &caller,
+                &self.position_token().get(),
+                new_position_id,
+                &BigUint::from(1u32),
+            );
+        }
+
+        self.add_unbonding_entry(&caller, token, amount - penalty + bonus);
+    }
+
+    /// Endpoint to withdraw the principal of the positions sent as payment while the emergency mode is
+    /// enabled. Their unclaimed rewards and redistributed penalties are forfeited, and neither the
+    /// early-exit penalty nor the unbonding period applies.
+    #[payable("*")]
+    #[endpoint(emergency_withdraw)]
+    fn emergency_withdraw(&self) {
+        self.require_emergency_mode();
+
+        let caller = self.blockchain().get_caller();
+        let receipts = self.call_value().all_esdt_transfers().clone_value();
+        require!(!receipts.is_empty(), "At least one position must be sent");
+
+        let mut principal = ManagedVec::new();
+        for receipt in receipts.iter() {
+            let position_id = self.require_position_receipt(&receipt);
+            let position = self.stake_position(position_id).get();
+            let token = position.token_identifier.clone();
+
+            // Shares are removed without settlement, so the rewards of the position are forfeited
+            self.remove_reward_shares(&position.owner, &position.shares);
+            self.decrease_stake(&position.owner, &token, &position.amount);
+            self.total_staked(&token).update(|total| *total -= &position.amount);
+            self.remove_position(position_id, &position.owner);
+
+            // Emit emergency withdraw event
+            self.emergency_withdraw_event(
+                caller.clone(),
+                position_id,
+                (token.clone(), position.amount.clone()),
+            );
+
+            principal.push(EsdtTokenPayment::new(token, 0, position.amount));
+        }
+
+        self.send().direct_multi(&caller, &principal);
+    }
+
+    /// Emit an event for creating a stake position
+    #[event("position_created_event")]
+    fn position_created_event(
+        &self,
+        #[indexed] user: ManagedAddress,
+        #[indexed] position_id: u64,
+        lock_epochs: u64,
+    );
+
+    /// Emit an event for migrating a legacy stake into a position
+    #[event("legacy_stake_migrated_event")]
+    fn legacy_stake_migrated_event(
+        &self,
+        #[indexed] user: ManagedAddress,
+        #[indexed] position_id: u64,
+        stake_data: (TokenIdentifier, BigUint),
+    );
+
     /// Emit an event for staking
     #[event("stake_event")]
     fn stake_event(
@@ -107,68 +417,146 @@ pub trait WinterStakingSc {
         stake_data: (TokenIdentifier, BigUint),
     );
 
-    /// Endpoint to claim rewards
+    /// Emit an event for registering a position to the holder of its receipt
+    #[event("position_transferred_event")]
+    fn position_transferred_event(
+        &self,
+        #[indexed] previous_owner: ManagedAddress,
+        #[indexed] new_owner: ManagedAddress,
+        position_id: u64,
+    );
+
+    /// Endpoint to claim rewards.
+    /// Position receipts sent with the call are registered to the caller and returned.
+    #[payable("*")]
     #[endpoint(claim_rewards)]
     fn claim_rewards(&self) {
         let caller = self.blockchain().get_caller();
+        self.register_received_positions(&caller);
+
+        let (reward_token, total_rewards) = self.mint_user_rewards(&caller);
+        self.pay_out_rewards(&caller, reward_token, total_rewards);
+    }
+
+    /// Endpoint for an approved keeper to claim the rewards of a user.
+    /// The keeper receives the tip set by the user, the rest goes to the user as with `claim_rewards`.
+    #[endpoint(claim_rewards_for)]
+    fn claim_rewards_for(&self, user: ManagedAddress) {
+        let keeper = self.blockchain().get_caller();
+        self.require_approved_keeper(&user, &keeper);
+
+        let (reward_token, total_rewards) = self.mint_user_rewards(&user);
+        let tip = total_rewards.clone() * self.keeper_tip(&user).get() / MULTIPLIER_DENOMINATOR;
+        if tip > 0 {
+            self.send().direct_esdt(&keeper, &reward_token, 0, &tip);
+        }
+
+        // Emit keeper claim event
+        self.keeper_claim_event(keeper, user.clone(), (reward_token.clone(), tip.clone()));
+
+        self.pay_out_rewards(&user, reward_token, total_rewards - tip);
+    }
+
+    /// Settles and mints the rewards of a user, at most once every 24 hours.
+    /// Returns the reward token and the minted amount.
+    fn mint_user_rewards(&self, user: &ManagedAddress) -> (TokenIdentifier, BigUint) {
+        self.require_not_paused();
+
         let current_timestamp = self.blockchain().get_block_timestamp();
-        let last_claim = self.last_reward_claim(&caller).get();
+        let last_claim = self.last_reward_claim(user).get();
 
         // Ensure 24 hours have passed since the last claim
-        let one_day_in_seconds = 24 * 60 * 60;
         require!(
-            current_timestamp >= last_claim + one_day_in_seconds,
+            current_timestamp >= last_claim + CLAIM_INTERVAL,
             "Rewards can only be claimed once every 24 hours"
         );
 
-        let mut total_rewards = BigUint::zero();
-        for ((user_address, token_identifier), staked_amount) in self.stakes().iter() {
-            if user_address == caller {
-                let reward = staked_amount.clone() / 100u64; // 1% of staked tokens
-                total_rewards += reward.clone();
-
-                // Emit reward distribution event
-                self.reward_event(user_address.clone(), token_identifier.clone(), reward.clone());
-            }
-        }
-
+        // Rewards accrue proportionally to the staked amount and the time it stayed staked
+        let total_rewards = self.take_user_rewards(user);
         require!(total_rewards > 0, "No rewards available to claim");
 
-        // Determine the reward recipient
-        let reward_recipient = if self.beneficiary(&caller).is_empty() {
-            caller.clone()
-        } else {
-            self.beneficiary(&caller).get()
-        };
-
         // Mint reward tokens
-        let reward_token = TokenIdentifier::from("SNOW-ab6b96".as_bytes());
+        self.require_reward_token_mintable();
+        let reward_token = self.reward_token().get();
         self.send().esdt_local_mint(
             &reward_token,
             0,
             &total_rewards,
         );
 
-        // Transfer rewards to the recipient
-        self.send().direct_esdt(
-            &reward_recipient,
-            &reward_token,
-            0,
-            &total_rewards,
-        );
-
         // Update the last reward claim timestamp
-        self.last_reward_claim(&caller).set(current_timestamp);
+        self.last_reward_claim(user).set(current_timestamp);
+
+        (reward_token, total_rewards)
     }
 
-    /// Emit an event for setting a beneficiary
-    #[event("beneficiary_set_event")]
-    fn beneficiary_set_event(
+    /// Restakes minted rewards when the user opted in and it is possible,
+    /// otherwise transfers them to the user's beneficiary, split recipients or the user
+    fn pay_out_rewards(
         &self,
-        #[indexed] user: ManagedAddress,
-        #[indexed] beneficiary: ManagedAddress,
-    );
+        user: &ManagedAddress,
+        reward_token: TokenIdentifier,
+        amount: BigUint,
+    ) {
+        if self.auto_compound(user).get() {
+            if let Some(payment) = self.compound_payment(&reward_token, &amount) {
+                let position_id =
+                    self.add_stake(user, &payment.token_identifier, &payment.amount, 0);
+                self.send().direct_esdt(
+                    user,
+                    &self.position_token().get(),
+                    position_id,
+                    &BigUint::from(1u32),
+                );
+
+                // Emit compounding event
+                self.compound_event(
+                    user.clone(),
+                    amount,
+                    (payment.token_identifier, payment.amount),
+                );
+                return;
+            }
+        }
+
+        // Transfer rewards to the beneficiary, the split recipients or the user
+        self.send_rewards(user, &reward_token, &amount);
+
+        // Emit reward distribution event
+        self.reward_event(user.clone(), reward_token, amount);
+    }
+
+    /// View endpoint returning the staked amount of each token of a user
+    #[view(getStake)]
+    fn get_stake(
+        &self,
+        user: ManagedAddress,
+    ) -> MultiValueEncoded<MultiValue2<TokenIdentifier, BigUint>> {
+        let mut result = MultiValueEncoded::new();
+
+        for token in self.user_staked_tokens(&user).iter() {
+            let amount = self.user_stake(&user, &token).get();
+            result.push((token, amount).into());
+        }
+
+        result
+    }
+
+    /// View endpoint returning the rewards a user can claim, including those not settled yet
+    #[view(getPendingRewards)]
+    fn get_pending_rewards(&self, user: ManagedAddress) -> BigUint {
+        self.pending_rewards(&user)
+    }
+
+    /// View endpoint returning the timestamp from which a user can claim rewards again
+    #[view(getNextClaimTimestamp)]
+    fn get_next_claim_timestamp(&self, user: ManagedAddress) -> u64 {
+        self.last_reward_claim(&user).get() + CLAIM_INTERVAL
+    }
 
+    /// Emit an event for setting the reward token
+    #[event("reward_token_set_event")]
+    fn reward_token_set_event(&self, #[indexed] reward_token: TokenIdentifier);
 
     /// Emit an event for rewards
     #[event("reward_event")]
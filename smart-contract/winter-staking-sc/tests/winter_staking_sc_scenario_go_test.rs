@@ -8,3 +8,73 @@ fn world() -> ScenarioWorld {
 fn empty_go() {
     world().run("scenarios/winter_staking_sc.scen.json");
 }
+
+#[test]
+fn reward_accrual_go() {
+    world().run("scenarios/reward_accrual.scen.json");
+}
+
+#[test]
+fn reward_splits_go() {
+    world().run("scenarios/reward_splits.scen.json");
+}
+
+#[test]
+fn keeper_claims_go() {
+    world().run("scenarios/keeper_claims.scen.json");
+}
+
+#[test]
+fn staking_views_go() {
+    world().run("scenarios/staking_views.scen.json");
+}
+
+#[test]
+fn pause_go() {
+    world().run("scenarios/pause.scen.json");
+}
+
+#[test]
+fn emergency_withdraw_go() {
+    world().run("scenarios/emergency_withdraw.scen.json");
+}
+
+#[test]
+fn lock_tiers_go() {
+    world().run("scenarios/lock_tiers.scen.json");
+}
+
+#[test]
+fn compounding_go() {
+    world().run("scenarios/compounding.scen.json");
+}
+
+#[test]
+fn unbonding_go() {
+    world().run("scenarios/unbonding.scen.json");
+}
+
+#[test]
+fn position_receipts_go() {
+    world().run("scenarios/position_receipts.scen.json");
+}
+
+#[test]
+fn emission_schedule_go() {
+    world().run("scenarios/emission_schedule.scen.json");
+}
+
+#[test]
+fn legacy_stakes_go() {
+    world().run("scenarios/legacy_stakes.scen.json");
+}
+
+#[test]
+fn reward_token_go() {
+    world().run("scenarios/reward_token.scen.json");
+}
+
+#[test]
+fn staking_tokens_go() {
+    world().run("scenarios/staking_tokens.scen.json");
+}
@@ -5,6 +5,7 @@ fn world() -> ScenarioWorld {
 
     // blockchain.set_current_dir_from_workspace("relative path to your workspace, if applicable");
     blockchain.register_contract("mxsc:output/winter-staking-sc.mxsc.json", winter_staking_sc::ContractBuilder);
+    blockchain.register_contract("mxsc:mock-pair/output/mock-pair.mxsc.json", mock_pair::ContractBuilder);
     blockchain
 }
 
@@ -12,3 +13,80 @@ fn world() -> ScenarioWorld {
 fn empty_rs() {
     world().run("scenarios/winter_staking_sc.scen.json");
 }
+
+#[test]
+fn reward_accrual_rs() {
+    world().run("scenarios/reward_accrual.scen.json");
+}
+
+#[test]
+fn reward_splits_rs() {
+    world().run("scenarios/reward_splits.scen.json");
+}
+
+#[test]
+fn keeper_claims_rs() {
+    world().run("scenarios/keeper_claims.scen.json");
+}
+
+#[test]
+fn staking_views_rs() {
+    world().run("scenarios/staking_views.scen.json");
+}
+
+#[test]
+fn pause_rs() {
+    world().run("scenarios/pause.scen.json");
+}
+
+#[test]
+fn emergency_withdraw_rs() {
+    world().run("scenarios/emergency_withdraw.scen.json");
+}
+
+#[test]
+fn lock_tiers_rs() {
+    world().run("scenarios/lock_tiers.scen.json");
+}
+
+#[test]
+fn compounding_rs() {
+    world().run("scenarios/compounding.scen.json");
+}
+
+#[test]
+fn unbonding_rs() {
+    world().run("scenarios/unbonding.scen.json");
+}
+
+#[test]
+fn position_receipts_rs() {
+    world().run("scenarios/position_receipts.scen.json");
+}
+
+#[test]
+fn emission_schedule_rs() {
+    world().run("scenarios/emission_schedule.scen.json");
+}
+
+#[test]
+fn legacy_stakes_rs() {
+    world().run("scenarios/legacy_stakes.scen.json");
+}
+
+#[test]
+fn reward_token_rs() {
+    world().run("scenarios/reward_token.scen.json");
+}
+
+#[test]
+fn staking_tokens_rs() {
+    world().run("scenarios/staking_tokens.scen.json");
+}
+
+// The other scenarios share a setup holding an already issued position token, while issuing it
+// relies on the ESDT system smart contract simulated by the Rust VM
+#[test]
+fn position_token_rs() {
+    world().run("scenarios/position_token.scen.json");
+}
@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                            3
-// Async Callback (empty):               1
-// Total number of exported functions:   6
+// Endpoints:                            63
+// Async Callback:                       1
+// Total number of exported functions:   66
 
 #![no_std]
 
@@ -20,10 +20,70 @@ multiversx_sc_wasm_adapter::endpoints! {
     (
         init => init
         upgrade => upgrade
-        set_beneficiary => set_beneficiary
+        get_reward_token => reward_token
+        set_reward_token => set_reward_token
+        getStakersCount => stakers_count
         stake_token_winter => stake_token_winter
+        register_positions => register_positions
+        unstake => unstake
+        emergency_withdraw => emergency_withdraw
         claim_rewards => claim_rewards
+        claim_rewards_for => claim_rewards_for
+        migrate_legacy_stakes => migrate_legacy_stakes
+        getStake => get_stake
+        getPendingRewards => get_pending_rewards
+        getNextClaimTimestamp => get_next_claim_timestamp
+        get_beneficiary => beneficiary
+        get_reward_splits => reward_splits
+        get_beneficiary_expiry => beneficiary_expiry
+        set_beneficiary => set_beneficiary
+        set_reward_splits => set_reward_splits
+        clear_beneficiary => clear_beneficiary
+        is_auto_compound => auto_compound
+        get_compound_swap_pair => compound_swap_pair
+        get_compound_token => compound_token
+        get_compound_max_slippage => compound_max_slippage
+        set_auto_compound => set_auto_compound
+        set_compound_swap => set_compound_swap
+        clear_compound_swap => clear_compound_swap
+        get_approved_keeper => approved_keeper
+        get_keeper_tip => keeper_tip
+        add_keeper => add_keeper
+        remove_keeper => remove_keeper
+        approve_keeper => approve_keeper
+        revoke_keeper => revoke_keeper
+        get_keepers => get_keepers
+        is_paused => paused
+        is_emergency_mode => emergency_mode
+        pause => pause
+        unpause => unpause
+        enable_emergency_mode => enable_emergency_mode
+        get_position_token => position_token
+        get_early_exit_penalty => early_exit_penalty
+        get_penalty_treasury => penalty_treasury
+        getTotalStaked => total_staked
+        get_undistributed_penalty => undistributed_penalty
+        issue_position_token => issue_position_token
+        set_lock_tier => set_lock_tier
+        remove_lock_tier => remove_lock_tier
+        set_early_exit_penalty => set_early_exit_penalty
+        set_penalty_treasury => set_penalty_treasury
+        clear_penalty_treasury => clear_penalty_treasury
+        get_lock_tiers => get_lock_tiers
+        get_user_positions => get_user_positions
+        getApr => get_apr
+        set_emission_schedule => set_emission_schedule
+        set_emission_steps => set_emission_steps
+        getEmissionInfo => get_emission_info
+        get_token_multiplier => token_multiplier
+        add_staking_token => add_staking_token
+        remove_staking_token => remove_staking_token
+        get_staking_tokens => get_staking_tokens
+        get_unbonding_period => unbonding_period
+        set_unbonding_period => set_unbonding_period
+        withdraw => withdraw
+        get_pending_unbonds => get_pending_unbonds
     )
 }
 
-multiversx_sc_wasm_adapter::async_callback_empty! {}
+multiversx_sc_wasm_adapter::async_callback! { winter_staking_sc }